@@ -0,0 +1,132 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::days::Part;
+use crate::error::{Error, Result};
+use crate::Answer;
+
+/// The known-good answers for the real inputs, one `<day> <part> <answer>` per line. Blank lines
+/// and lines starting with `#` are ignored.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExpectedAnswers(BTreeMap<(usize, Part), String>);
+
+/// How an answer compares with the expected one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Match,
+    Mismatch {
+        expected: String,
+    },
+    /// There's nothing to compare against; not a regression.
+    Unrecorded,
+}
+
+impl ExpectedAnswers {
+    pub fn default_path() -> PathBuf {
+        env::current_dir().unwrap_or_default().join("answers.txt")
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut answers = BTreeMap::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let malformed = || {
+                Error::Parse(format!(
+                    "answers line {}: expected <day> <part> <answer>, got {:?}",
+                    index + 1,
+                    line
+                ))
+            };
+            let mut fields = line.splitn(3, char::is_whitespace);
+            let day = fields
+                .next()
+                .and_then(|d| d.parse().ok())
+                .ok_or_else(malformed)?;
+            let part = fields.next().and_then(Part::parse).ok_or_else(malformed)?;
+            let answer = fields.next().map(str::trim).ok_or_else(malformed)?;
+            if answers.insert((day, part), answer.to_owned()).is_some() {
+                return Err(Error::Parse(format!(
+                    "answers line {}: day {} part {} is listed twice",
+                    index + 1,
+                    day,
+                    part
+                )));
+            }
+        }
+        Ok(Self(answers))
+    }
+
+    pub fn get(&self, day: usize, part: Part) -> Option<&str> {
+        self.0.get(&(day, part)).map(String::as_str)
+    }
+
+    pub fn check(&self, day: usize, part: Part, actual: &Answer) -> Verdict {
+        match self.get(day, part) {
+            None => Verdict::Unrecorded,
+            Some(expected) if expected == actual.to_string() => Verdict::Match,
+            Some(expected) => Verdict::Mismatch {
+                expected: expected.to_owned(),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const ANSWERS: &str = "\
+# day part answer
+1 1 514579
+1 2 241861950
+
+20 1 20899048083289
+";
+
+    #[test]
+    fn parse() {
+        let answers = ExpectedAnswers::parse(ANSWERS).unwrap();
+        assert_eq!(answers.get(1, Part::Two), Some("241861950"));
+        assert_eq!(answers.get(20, Part::One), Some("20899048083289"));
+        assert_eq!(answers.get(20, Part::Two), None);
+    }
+
+    #[test]
+    fn check() {
+        let answers = ExpectedAnswers::parse(ANSWERS).unwrap();
+        assert_eq!(
+            answers.check(1, Part::One, &Answer::Number(514579)),
+            Verdict::Match
+        );
+        assert_eq!(
+            answers.check(1, Part::Two, &Answer::Number(1)),
+            Verdict::Mismatch {
+                expected: "241861950".to_owned()
+            }
+        );
+        assert_eq!(
+            answers.check(20, Part::Two, &Answer::Unsolved),
+            Verdict::Unrecorded
+        );
+    }
+
+    #[test]
+    fn malformed() {
+        assert!(ExpectedAnswers::parse("1 3 42").is_err());
+        assert!(ExpectedAnswers::parse("1 1").is_err());
+        assert!(ExpectedAnswers::parse("1 1 42\n1 1 43").is_err());
+    }
+}
@@ -0,0 +1,203 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::days::{Day, Part};
+use crate::error::{Error, Result};
+
+/// A median has to grow by this factor, and by at least `NOISE_FLOOR_MS`, before we call it a
+/// regression. Anything tighter flags noise.
+pub const REGRESSION_FACTOR: f64 = 1.25;
+pub const NOISE_FLOOR_MS: f64 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Phase {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(match self {
+            Phase::Parse => "parse",
+            Phase::Part1 => "part 1",
+            Phase::Part2 => "part 2",
+        })
+    }
+}
+
+/// Timings for one phase of one day, in milliseconds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    pub day: usize,
+    pub phase: Phase,
+    pub min_ms: f64,
+    pub median_ms: f64,
+    pub max_ms: f64,
+}
+
+impl Record {
+    fn from_samples(day: usize, phase: Phase, mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+        Self {
+            day,
+            phase,
+            min_ms: ms(samples[0]),
+            median_ms: ms(samples[samples.len() / 2]),
+            max_ms: ms(samples[samples.len() - 1]),
+        }
+    }
+
+    /// Is this noticeably slower than `previous`?
+    pub fn regressed_from(&self, previous: &Record) -> bool {
+        self.median_ms > previous.median_ms * REGRESSION_FACTOR
+            && self.median_ms - previous.median_ms > NOISE_FLOOR_MS
+    }
+}
+
+/// Time parsing and both parts of a day, `reps` times over.
+pub fn run(day: &Day, input: &str, reps: usize) -> Result<Vec<Record>> {
+    assert!(reps > 0, "need at least one repetition");
+    let mut samples = [Vec::new(), Vec::new(), Vec::new()];
+    for _ in 0..reps {
+        let start = Instant::now();
+        let parsed = day.parse(black_box(input))?;
+        samples[0].push(start.elapsed());
+
+        for (i, &part) in [Part::One, Part::Two].iter().enumerate() {
+            let start = Instant::now();
+            black_box(day.solve(part, &parsed));
+            samples[i + 1].push(start.elapsed());
+        }
+    }
+    let [parse, part1, part2] = samples;
+    Ok(vec![
+        Record::from_samples(day.number, Phase::Parse, parse),
+        Record::from_samples(day.number, Phase::Part1, part1),
+        Record::from_samples(day.number, Phase::Part2, part2),
+    ])
+}
+
+/// The saved results of previous benchmark runs.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Report {
+    pub records: Vec<Record>,
+}
+
+impl Report {
+    pub fn default_path() -> PathBuf {
+        env::current_dir()
+            .unwrap_or_default()
+            .join("benchmarks.json")
+    }
+
+    /// Load a saved report; a file which doesn't exist yet is an empty report.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(Error::Io {
+                    path: path.to_owned(),
+                    source,
+                })
+            }
+        };
+        serde_json::from_str(&text).map_err(|e| {
+            Error::Parse(format!(
+                "{} isn't a benchmark report: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("a report is always serialisable");
+        fs::write(path, json + "\n").map_err(|source| Error::Io {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    pub fn get(&self, day: usize, phase: Phase) -> Option<&Record> {
+        self.records
+            .iter()
+            .find(|r| r.day == day && r.phase == phase)
+    }
+
+    /// Replace any records for the same day and phase, keeping the rest.
+    pub fn update(&mut self, records: Vec<Record>) {
+        for record in records {
+            self.records
+                .retain(|r| r.day != record.day || r.phase != record.phase);
+            self.records.push(record);
+        }
+        self.records.sort_by_key(|r| (r.day, r.phase as u8));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    fn record(day: usize, phase: Phase, median_ms: f64) -> Record {
+        Record {
+            day,
+            phase,
+            min_ms: median_ms,
+            median_ms,
+            max_ms: median_ms,
+        }
+    }
+
+    #[test]
+    fn statistics() {
+        let samples = [5, 1, 4, 2, 3].iter().map(|&ms| Duration::from_millis(ms));
+        let r = Record::from_samples(1, Phase::Parse, samples.collect());
+        assert_eq!((r.min_ms, r.median_ms, r.max_ms), (1.0, 3.0, 5.0));
+    }
+
+    #[test]
+    fn regressions() {
+        let before = record(15, Phase::Part2, 100.0);
+        assert!(record(15, Phase::Part2, 200.0).regressed_from(&before));
+        assert!(!record(15, Phase::Part2, 110.0).regressed_from(&before));
+        // Doubling is within the noise when the times are tiny.
+        let tiny = record(1, Phase::Parse, 0.01);
+        assert!(!record(1, Phase::Parse, 0.02).regressed_from(&tiny));
+    }
+
+    #[test]
+    fn run_and_round_trip() {
+        let day = days::find(1).unwrap();
+        let records = run(day, "1721\n979\n366\n299\n675\n1456\n", 3).unwrap();
+        assert_eq!(records.len(), 3);
+
+        let mut report = Report::default();
+        report.update(vec![record(2, Phase::Parse, 1.0)]);
+        report.update(records);
+        report.update(vec![record(2, Phase::Parse, 2.0)]);
+        assert_eq!(report.records.len(), 4);
+        assert_eq!(report.records[0].day, 1);
+        assert_eq!(report.get(2, Phase::Parse).unwrap().median_ms, 2.0);
+
+        // Measured times needn't survive JSON to the last bit, so save round ones.
+        let report = Report {
+            records: vec![record(1, Phase::Part1, 0.25), record(2, Phase::Parse, 2.0)],
+        };
+        let path = env::temp_dir().join("aoc-2020-bench-report.json");
+        report.save(&path).unwrap();
+        assert_eq!(Report::load(&path).unwrap(), report);
+        fs::remove_file(path).unwrap();
+    }
+}
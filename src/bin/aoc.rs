@@ -0,0 +1,723 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::panic;
+use std::path::PathBuf;
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use aoc_2020::answers::{ExpectedAnswers, Verdict};
+use aoc_2020::bench::{self, Report};
+use aoc_2020::client::{Client, Fetched, Submission};
+use aoc_2020::days::{self, Day, Part, PARTS};
+use aoc_2020::generate::{self, Mode};
+use aoc_2020::recording;
+use aoc_2020::runner::{self, Status};
+use aoc_2020::submit::{History, Response};
+use aoc_2020::watch::{self, Answers, Snapshot};
+use aoc_2020::{Answer, InputSource};
+
+const USAGE: &str = "\
+Usage (any command takes -v or -vv to log what the solvers are up to):
+    aoc run <day> [--part <1|2>] [--input <path|->] [--format <text|json>]
+    aoc run all [--part <1|2>] [--format <text|json>] [--jobs <n>]
+    aoc verify [<day>|all] [--answers <path>]
+    aoc bench [<day>|all] [--reps <n>] [--save <path>]
+    aoc fetch [<day>|all]
+    aoc submit <day> <1|2> [<answer>] [--history <path>]
+    aoc watch <day> [--interval <ms>]
+    aoc generate <day> [--size <n>] [--seed <n>] [--expected <path>]
+    aoc animate <day> [--part <1|2>] [--input <path|->] [--delay <ms>] [--export <path>]
+
+Inputs are read from ./input/day<N>.txt unless --input or $AOC_INPUT says otherwise.
+run all works through the days on one thread per CPU and reports them in a table; a day which
+fails or panics doesn't stop the others.
+verify always uses ./input/day<N>.txt, and compares against ./answers.txt by default.
+bench times each day 5 times unless told otherwise, compares medians with the previous run
+saved in ./benchmarks.json, then saves its own results there.
+--format json prints one JSON object per line, with fields day, part, answer and elapsed_ms;
+elapsed_ms covers solving the part, not reading or parsing the input.
+fetch downloads inputs to wherever run looks for them, skipping any already there. It needs
+$AOC_SESSION set to the site's session cookie; $AOC_URL points it at a server other than
+https://adventofcode.com/2020.
+submit sends an answer, or if none is given the one run computes. Every attempt is recorded in
+./submissions.json by default, and answers that history shows to be wrong aren't sent again.
+watch checks the sources under ./src and the day's input every 500ms unless told otherwise, and
+after each change reruns the day's tests and then the day itself, reporting answers that changed.
+generate prints a random input about the size of a real one unless --size says otherwise; the
+same --seed always gives the same input. --expected also saves the answers, in the format of an
+example's .expected file.
+animate plays back every generation of day 11's or day 17's automaton, part 1 unless told
+otherwise, waiting 200ms between frames unless --delay says otherwise. --export saves the frames
+to a text file instead.
+Logs go to stderr. $AOC_LOG takes a filter such as debug or aoc_2020::day20=trace.";
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n\n{}", message, USAGE);
+    process::exit(2);
+}
+
+fn fail(error: aoc_2020::Error) -> ! {
+    eprintln!("error: {}", error);
+    process::exit(1);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+#[derive(Serialize)]
+struct Record {
+    day: usize,
+    part: Part,
+    answer: Answer,
+    elapsed_ms: f64,
+}
+
+fn print_json(day: usize, part: Part, answer: Answer, elapsed: Duration) {
+    let record = Record {
+        day,
+        part,
+        answer,
+        elapsed_ms: elapsed.as_secs_f64() * 1000.0,
+    };
+    println!("{}", serde_json::to_string(&record).unwrap());
+}
+
+fn run_day(
+    day: &Day,
+    parts: &[Part],
+    source: &InputSource,
+    format: Format,
+) -> aoc_2020::Result<()> {
+    let parsed = day.parse(&source.try_content()?)?;
+    for &part in parts {
+        let start = Instant::now();
+        let answer = day.solve(part, &parsed);
+        let elapsed = start.elapsed();
+        match format {
+            Format::Text => println!("Day {} part {}: {}", day.number, part, answer),
+            Format::Json => print_json(day.number, part, answer, elapsed),
+        }
+    }
+    Ok(())
+}
+
+fn run_all(parts: &[Part], format: Format, jobs: usize) {
+    // The table reports panics, so the default hook's messages would only be clutter.
+    panic::set_hook(Box::new(|_| {}));
+    let all: Vec<&Day> = days::DAYS.iter().collect();
+    let outcomes = runner::run_days(&all, parts, jobs, InputSource::for_day);
+    let _ = panic::take_hook();
+
+    let failed = outcomes
+        .iter()
+        .filter(|o| o.status != Status::Solved)
+        .count();
+    match format {
+        Format::Json => {
+            for o in outcomes {
+                match (o.answer, o.status) {
+                    (Some(answer), _) => print_json(o.day, o.part, answer, o.elapsed),
+                    (None, Status::Failed(e)) => eprintln!("error: day {}: {}", o.day, e),
+                    (None, status) => {
+                        eprintln!("error: day {} part {}: {:?}", o.day, o.part, status)
+                    }
+                }
+            }
+        }
+        Format::Text => {
+            let answers: Vec<String> = outcomes
+                .iter()
+                .map(|o| o.answer.as_ref().map_or("-".to_owned(), Answer::to_string))
+                .collect();
+            let width = answers.iter().map(String::len).max().unwrap_or(0).max(6);
+            println!(
+                "{:>3}  {:>4}  {:width$}  {:>10}  status",
+                "day",
+                "part",
+                "answer",
+                "time ms",
+                width = width
+            );
+            for (o, answer) in outcomes.iter().zip(answers) {
+                let status = match &o.status {
+                    Status::Solved => "ok".to_owned(),
+                    Status::Failed(e) => format!("failed: {}", e),
+                    Status::Panicked(message) => format!("panicked: {}", message),
+                };
+                println!(
+                    "{:>3}  {:>4}  {:width$}  {:>10.3}  {}",
+                    o.day,
+                    o.part,
+                    answer,
+                    o.elapsed.as_secs_f64() * 1000.0,
+                    status,
+                    width = width
+                );
+            }
+        }
+    }
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn run(mut args: impl Iterator<Item = String>) {
+    let which = args
+        .next()
+        .unwrap_or_else(|| usage_error("Which day should I run?"));
+    let mut parts: &[Part] = &PARTS;
+    let mut source = None;
+    let mut format = Format::Text;
+    let mut jobs = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--jobs" => {
+                jobs = Some(
+                    args.next()
+                        .and_then(|n| n.parse().ok())
+                        .filter(|&n| n > 0)
+                        .unwrap_or_else(|| usage_error("--jobs expects a positive number")),
+                );
+            }
+            "--format" => {
+                format = match args.next().as_deref() {
+                    Some("text") => Format::Text,
+                    Some("json") => Format::Json,
+                    _ => usage_error("--format expects text or json"),
+                };
+            }
+            "--part" => {
+                let part = args
+                    .next()
+                    .and_then(|p| Part::parse(&p))
+                    .unwrap_or_else(|| usage_error("--part expects 1 or 2"));
+                parts = match part {
+                    Part::One => &PARTS[..1],
+                    Part::Two => &PARTS[1..],
+                };
+            }
+            "--input" => {
+                let arg = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path, or - for stdin"));
+                source = Some(InputSource::from_arg(&arg));
+            }
+            other => usage_error(&format!("Unexpected argument {:?}", other)),
+        }
+    }
+
+    if which == "all" {
+        if source.is_some() {
+            usage_error("--input can only be given when running a single day");
+        }
+        let jobs = jobs.unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+        run_all(parts, format, jobs);
+    } else {
+        if jobs.is_some() {
+            usage_error("--jobs only makes sense with run all");
+        }
+        let day = parse_day(&which);
+        let source = source.unwrap_or_else(|| InputSource::for_day(day.number));
+        run_day(day, parts, &source, format).unwrap_or_else(|e| fail(e));
+    }
+}
+
+fn parse_day(which: &str) -> &'static Day {
+    which
+        .parse()
+        .ok()
+        .and_then(days::find)
+        .unwrap_or_else(|| usage_error(&format!("No such day {:?}", which)))
+}
+
+fn verify(mut args: impl Iterator<Item = String>) {
+    let mut which = None;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--answers" => {
+                let arg = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--answers expects a path"));
+                path = Some(arg.into());
+            }
+            _ if which.is_none() => which = Some(arg),
+            other => usage_error(&format!("Unexpected argument {:?}", other)),
+        }
+    }
+    let path = path.unwrap_or_else(ExpectedAnswers::default_path);
+    let expected = ExpectedAnswers::load(&path).unwrap_or_else(|e| fail(e));
+    let to_check: Vec<&Day> = match which.as_deref() {
+        None | Some("all") => days::DAYS.iter().collect(),
+        Some(which) => vec![parse_day(which)],
+    };
+
+    // Only the interesting rows make it into the table: `-`/`+` pairs for regressions, `?` for
+    // answers with nothing to compare against, and `!` for days which didn't run at all.
+    let mut rows = Vec::new();
+    let (mut matched, mut regressed, mut unrecorded, mut failed) = (0, 0, 0, 0);
+    for day in to_check {
+        let source = InputSource::Path(InputSource::default_path(day.number));
+        let parsed = match source.try_content().and_then(|input| day.parse(&input)) {
+            Ok(parsed) => parsed,
+            Err(e) => {
+                rows.push(format!("! {:>3}  {:>4}  {}", day.number, "-", e));
+                failed += 1;
+                continue;
+            }
+        };
+        for &part in &PARTS {
+            let actual = day.solve(part, &parsed);
+            match expected.check(day.number, part, &actual) {
+                Verdict::Match => matched += 1,
+                Verdict::Mismatch { expected } => {
+                    rows.push(format!("- {:>3}  {:>4}  {}", day.number, part, expected));
+                    rows.push(format!("+ {:>3}  {:>4}  {}", day.number, part, actual));
+                    regressed += 1;
+                }
+                Verdict::Unrecorded if actual == Answer::Unsolved => {}
+                Verdict::Unrecorded => {
+                    rows.push(format!("? {:>3}  {:>4}  {}", day.number, part, actual));
+                    unrecorded += 1;
+                }
+            }
+        }
+    }
+
+    if !rows.is_empty() {
+        println!("  {:>3}  {:>4}  answer", "day", "part");
+        for row in rows {
+            println!("{}", row);
+        }
+        println!();
+    }
+    println!(
+        "{} matched, {} regressed, {} unrecorded, {} failed to run",
+        matched, regressed, unrecorded, failed
+    );
+    if regressed > 0 || failed > 0 {
+        process::exit(1);
+    }
+}
+
+fn benchmark(mut args: impl Iterator<Item = String>) {
+    let mut which = None;
+    let mut reps = 5;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--reps" => {
+                reps = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .unwrap_or_else(|| usage_error("--reps expects a positive number"));
+            }
+            "--save" => {
+                let arg = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--save expects a path"));
+                path = Some(arg.into());
+            }
+            _ if which.is_none() => which = Some(arg),
+            other => usage_error(&format!("Unexpected argument {:?}", other)),
+        }
+    }
+    let path = path.unwrap_or_else(Report::default_path);
+    let mut report = Report::load(&path).unwrap_or_else(|e| fail(e));
+    let to_time: Vec<&Day> = match which.as_deref() {
+        None | Some("all") => days::DAYS.iter().collect(),
+        Some(which) => vec![parse_day(which)],
+    };
+    if cfg!(debug_assertions) {
+        eprintln!("warning: this is a debug build, so the timings won't mean much");
+    }
+
+    println!(
+        "{:>3}  {:6}  {:>10}  {:>10}  {:>10}",
+        "day", "phase", "min ms", "median ms", "max ms"
+    );
+    let mut regressions = 0;
+    let mut failed = false;
+    for day in to_time {
+        let input = InputSource::for_day(day.number).try_content();
+        let records = match input.and_then(|input| bench::run(day, &input, reps)) {
+            Ok(records) => records,
+            Err(e) => {
+                eprintln!("error: day {}: {}", day.number, e);
+                failed = true;
+                continue;
+            }
+        };
+        for record in &records {
+            let verdict = match report.get(record.day, record.phase) {
+                Some(previous) if record.regressed_from(previous) => {
+                    regressions += 1;
+                    format!("  REGRESSED (median was {:.3})", previous.median_ms)
+                }
+                _ => String::new(),
+            };
+            println!(
+                "{:>3}  {:6}  {:>10.3}  {:>10.3}  {:>10.3}{}",
+                record.day, record.phase, record.min_ms, record.median_ms, record.max_ms, verdict
+            );
+        }
+        report.update(records);
+    }
+
+    report.save(&path).unwrap_or_else(|e| fail(e));
+    if regressions > 0 {
+        println!("\n{} phase(s) got slower since the last run", regressions);
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+fn fetch(mut args: impl Iterator<Item = String>) {
+    let which = args.next();
+    if let Some(other) = args.next() {
+        usage_error(&format!("Unexpected argument {:?}", other));
+    }
+    let to_fetch: Vec<&Day> = match which.as_deref() {
+        None | Some("all") => days::DAYS.iter().collect(),
+        Some(which) => vec![parse_day(which)],
+    };
+    let client = Client::from_env().unwrap_or_else(|e| fail(e));
+    for day in to_fetch {
+        let path = match InputSource::for_day(day.number) {
+            InputSource::Path(path) => path,
+            _ => usage_error("fetch can only save inputs to a file; check $AOC_INPUT"),
+        };
+        match client.fetch_input(day.number, &path) {
+            Ok(Fetched::Cached) => {
+                println!("Day {}: already saved at {}", day.number, path.display())
+            }
+            Ok(Fetched::Downloaded { bytes }) => {
+                println!(
+                    "Day {}: saved {} bytes to {}",
+                    day.number,
+                    bytes,
+                    path.display()
+                )
+            }
+            Err(e) => fail(e),
+        }
+    }
+}
+
+fn submit(mut args: impl Iterator<Item = String>) {
+    let day = parse_day(
+        &args
+            .next()
+            .unwrap_or_else(|| usage_error("Which day's answer is this?")),
+    );
+    let part = args
+        .next()
+        .and_then(|p| Part::parse(&p))
+        .unwrap_or_else(|| usage_error("Which part's answer is this? Expected 1 or 2"));
+    let mut answer = None;
+    let mut path = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--history" => {
+                let arg = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--history expects a path"));
+                path = Some(arg.into());
+            }
+            _ if answer.is_none() => answer = Some(arg),
+            other => usage_error(&format!("Unexpected argument {:?}", other)),
+        }
+    }
+    let answer = answer.unwrap_or_else(|| {
+        let input = InputSource::for_day(day.number)
+            .try_content()
+            .unwrap_or_else(|e| fail(e));
+        let parsed = day.parse(&input).unwrap_or_else(|e| fail(e));
+        match day.solve(part, &parsed) {
+            Answer::Unsolved => {
+                eprintln!("error: day {} part {} isn't solved yet", day.number, part);
+                process::exit(1);
+            }
+            answer => answer.to_string(),
+        }
+    });
+
+    let path = path.unwrap_or_else(History::default_path);
+    let mut history = History::load(&path).unwrap_or_else(|e| fail(e));
+    let client = Client::from_env().unwrap_or_else(|e| fail(e));
+    let submission = client.submit(&mut history, day.number, part, &answer);
+    let response = match submission.unwrap_or_else(|e| fail(e)) {
+        Submission::Known(response) => {
+            println!(
+                "Not sending {} for day {} part {}: it's known to be {}",
+                answer, day.number, part, response
+            );
+            response
+        }
+        Submission::Sent(response) => {
+            history.save(&path).unwrap_or_else(|e| fail(e));
+            println!(
+                "Day {} part {}: {} is {}",
+                day.number, part, answer, response
+            );
+            response
+        }
+    };
+    if response != Response::Right {
+        process::exit(1);
+    }
+}
+
+fn watch(mut args: impl Iterator<Item = String>) {
+    let day = parse_day(
+        &args
+            .next()
+            .unwrap_or_else(|| usage_error("Which day should I watch?")),
+    );
+    let mut interval = Duration::from_millis(500);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--interval" => {
+                interval = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|&n| n > 0)
+                    .map(Duration::from_millis)
+                    .unwrap_or_else(|| usage_error("--interval expects a positive number"));
+            }
+            other => usage_error(&format!("Unexpected argument {:?}", other)),
+        }
+    }
+    let root = env::current_dir().unwrap_or_default();
+    let input = match InputSource::for_day(day.number) {
+        InputSource::Path(path) => path,
+        _ => usage_error("watch can only follow an input file; check $AOC_INPUT"),
+    };
+
+    let mut answers = Answers::new();
+    let mut snapshot = Snapshot::take(&root, day.number, &input);
+    loop {
+        match watch::run_tests(&root, day.number)
+            .and_then(|_| watch::run_solver(&root, day.number, &input))
+        {
+            Ok(latest) => {
+                let diff = watch::answer_diff(&answers, &latest);
+                if diff.is_empty() {
+                    println!("Day {}: answers unchanged", day.number);
+                }
+                for line in diff {
+                    println!("Day {} {}", day.number, line);
+                }
+                answers = latest;
+            }
+            Err(output) => eprintln!(
+                "{}\nDay {}: failed; see above",
+                output.trim_end(),
+                day.number
+            ),
+        }
+
+        // Wait for something to change, then for the editor to finish saving.
+        let changed = loop {
+            thread::sleep(interval);
+            let latest = Snapshot::take(&root, day.number, &input);
+            let changed: Vec<PathBuf> = snapshot
+                .changes(&latest)
+                .into_iter()
+                .map(|p| p.strip_prefix(&root).unwrap_or(p).to_owned())
+                .collect();
+            if !changed.is_empty() {
+                snapshot = latest;
+                break changed;
+            }
+        };
+        loop {
+            thread::sleep(interval);
+            let latest = Snapshot::take(&root, day.number, &input);
+            if latest == snapshot {
+                break;
+            }
+            snapshot = latest;
+        }
+        for path in changed {
+            eprintln!("changed: {}", path.display());
+        }
+    }
+}
+
+fn generate(mut args: impl Iterator<Item = String>) {
+    let day = parse_day(
+        &args
+            .next()
+            .unwrap_or_else(|| usage_error("Which day's input should I make?")),
+    );
+    let generator = generate::find(day.number).expect("every day has a generator");
+    let mut size = generator.default_size;
+    let mut seed = None;
+    let mut expected = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                size = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .filter(|n| generator.sizes.contains(n))
+                    .unwrap_or_else(|| {
+                        usage_error(&format!(
+                            "--size expects a number of {} from {} to {}",
+                            generator.unit,
+                            generator.sizes.start(),
+                            generator.sizes.end()
+                        ))
+                    });
+            }
+            "--seed" => {
+                seed = Some(
+                    args.next()
+                        .and_then(|n| n.parse().ok())
+                        .unwrap_or_else(|| usage_error("--seed expects a number")),
+                );
+            }
+            "--expected" => {
+                let arg = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--expected expects a path"));
+                expected = Some(PathBuf::from(arg));
+            }
+            other => usage_error(&format!("Unexpected argument {:?}", other)),
+        }
+    }
+    let seed = seed.unwrap_or_else(|| {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_nanos() as u64)
+    });
+    eprintln!(
+        "Day {}: {} {}, seed {}",
+        day.number, size, generator.unit, seed
+    );
+    let mode = match expected {
+        Some(_) => Mode::Known,
+        None => Mode::Random,
+    };
+    let generated = generator.generate(seed, size, mode);
+    print!("{}", generated.input);
+    if let Some(path) = expected {
+        fs::write(&path, generated.expected_file())
+            .map_err(|source| aoc_2020::Error::Io { path, source })
+            .unwrap_or_else(|e| fail(e));
+    }
+}
+
+fn animate(mut args: impl Iterator<Item = String>) {
+    let day = parse_day(
+        &args
+            .next()
+            .unwrap_or_else(|| usage_error("Which day should I animate?")),
+    );
+    let recorder = recording::find(day.number).unwrap_or_else(|| {
+        usage_error(&format!(
+            "Day {} has nothing to animate; try day 11 or 17",
+            day.number
+        ))
+    });
+    let mut part = Part::One;
+    let mut source = InputSource::for_day(day.number);
+    let mut delay = Duration::from_millis(200);
+    let mut export = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                part = args
+                    .next()
+                    .and_then(|p| Part::parse(&p))
+                    .unwrap_or_else(|| usage_error("--part expects 1 or 2"));
+            }
+            "--input" => {
+                let arg = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--input expects a path, or - for stdin"));
+                source = InputSource::from_arg(&arg);
+            }
+            "--delay" => {
+                delay = args
+                    .next()
+                    .and_then(|n| n.parse().ok())
+                    .map(Duration::from_millis)
+                    .unwrap_or_else(|| usage_error("--delay expects a number of milliseconds"));
+            }
+            "--export" => {
+                let arg = args
+                    .next()
+                    .unwrap_or_else(|| usage_error("--export expects a path"));
+                export = Some(PathBuf::from(arg));
+            }
+            other => usage_error(&format!("Unexpected argument {:?}", other)),
+        }
+    }
+    let input = source.try_content().unwrap_or_else(|e| fail(e));
+    let recording = recorder
+        .record(&input, part)
+        .unwrap_or_else(|e| fail(e.into()));
+    match export {
+        Some(path) => fs::write(&path, recording.export())
+            .map_err(|source| aoc_2020::Error::Io { path, source })
+            .unwrap_or_else(|e| fail(e)),
+        None => recording
+            .play(&mut io::stdout().lock(), delay)
+            .unwrap_or_else(|source| {
+                fail(aoc_2020::Error::Io {
+                    path: PathBuf::from("-"),
+                    source,
+                })
+            }),
+    }
+}
+
+/// Environment variable holding an `env_logger`-style filter, like `debug` or `aoc_2020::day20=trace`.
+const LOG_VAR: &str = "AOC_LOG";
+
+/// Logging is off unless `$AOC_LOG` says otherwise; `-v` turns on debug messages everywhere and
+/// `-vv` trace messages as well.
+fn init_logging(verbosity: usize) {
+    let mut builder =
+        env_logger::Builder::from_env(env_logger::Env::new().filter_or(LOG_VAR, "off"));
+    match verbosity {
+        0 => {}
+        1 => {
+            builder.filter_level(log::LevelFilter::Debug);
+        }
+        _ => {
+            builder.filter_level(log::LevelFilter::Trace);
+        }
+    }
+    builder.init();
+}
+
+fn main() {
+    let (flags, args): (Vec<_>, Vec<_>) =
+        env::args().skip(1).partition(|a| a == "-v" || a == "-vv");
+    init_logging(flags.iter().map(|f| f.len() - 1).sum());
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        Some("run") => run(args),
+        Some("verify") => verify(args),
+        Some("bench") => benchmark(args),
+        Some("fetch") => fetch(args),
+        Some("submit") => submit(args),
+        Some("watch") => watch(args),
+        Some("generate") => generate(args),
+        Some("animate") => animate(args),
+        Some(other) => usage_error(&format!("Unknown command {:?}", other)),
+        None => usage_error("No command given"),
+    }
+}
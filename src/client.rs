@@ -0,0 +1,321 @@
+//! Talking to the puzzle server: downloading inputs, which are cached on disk so that each one is
+//! only ever fetched once, and submitting answers.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::time::Duration;
+
+use crate::days::Part;
+use crate::error::{Error, Result};
+use crate::submit::{History, Response};
+
+/// Environment variable holding the value of the puzzle site's `session` cookie.
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+/// Environment variable which overrides `DEFAULT_BASE_URL`, say to point at a stand-in server.
+pub const URL_VAR: &str = "AOC_URL";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com/2020";
+
+/// The site asks that automated requests say where they come from.
+const USER_AGENT: &str = concat!(
+    "aoc-2020/",
+    env!("CARGO_PKG_VERSION"),
+    " (",
+    env!("CARGO_PKG_AUTHORS"),
+    ")"
+);
+
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+/// What `Client::fetch_input` had to do.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fetched {
+    /// The input was already saved, so the server wasn't bothered.
+    Cached,
+    Downloaded {
+        bytes: usize,
+    },
+}
+
+/// What became of an answer passed to `Client::submit`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Submission {
+    /// The history already knew the verdict, so the answer wasn't sent.
+    Known(Response),
+    Sent(Response),
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// A client for the server at `base_url`, such as `DEFAULT_BASE_URL`, which identifies itself
+    /// with the given session cookie.
+    pub fn new(base_url: &str, session: &str) -> Result<Self> {
+        let session = session.trim();
+        if session.is_empty() {
+            return Err(Error::NoSession);
+        }
+        Ok(Self {
+            base_url: base_url.trim_end_matches('/').to_owned(),
+            session: session.to_owned(),
+            agent: ureq::AgentBuilder::new()
+                .timeout(TIMEOUT)
+                .user_agent(USER_AGENT)
+                .build(),
+        })
+    }
+
+    /// A client configured by `$AOC_SESSION` and, optionally, `$AOC_URL`.
+    pub fn from_env() -> Result<Self> {
+        let session = env::var(SESSION_VAR).map_err(|_| Error::NoSession)?;
+        let base_url = env::var(URL_VAR).unwrap_or_else(|_| DEFAULT_BASE_URL.to_owned());
+        Self::new(&base_url, &session)
+    }
+
+    fn url(&self, day: usize, rest: &str) -> String {
+        format!("{}/day/{}{}", self.base_url, day, rest)
+    }
+
+    /// Make sure the input for `day` is saved at `path`, downloading it if it isn't there yet.
+    /// A file which already exists is never replaced; delete it to fetch it again.
+    pub fn fetch_input(&self, day: usize, path: &Path) -> Result<Fetched> {
+        if path.exists() {
+            return Ok(Fetched::Cached);
+        }
+        let url = self.url(day, "/input");
+        debug!("fetching {}", url);
+        let request = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session));
+        let input = read_body(&url, request.call())?;
+
+        // Write somewhere else first, so that an interrupted download can't pass for the input.
+        let io_error = |source| Error::Io {
+            path: path.to_owned(),
+            source,
+        };
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(io_error)?;
+        }
+        let partial = path.with_extension("part");
+        fs::write(&partial, &input)
+            .and_then(|_| fs::rename(&partial, path))
+            .map_err(io_error)?;
+        Ok(Fetched::Downloaded { bytes: input.len() })
+    }
+
+    /// Send `answer` for one part of a day, unless `history` already knows what the server would
+    /// say. Whatever the server does say is added to `history`, which the caller should save.
+    pub fn submit(
+        &self,
+        history: &mut History,
+        day: usize,
+        part: Part,
+        answer: &str,
+    ) -> Result<Submission> {
+        let answer = answer.trim();
+        if let Some(response) = history.known(day, part, answer) {
+            return Ok(Submission::Known(response));
+        }
+        let url = self.url(day, "/answer");
+        debug!("submitting {:?} to {}", answer, url);
+        let level = part.to_string();
+        let request = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session));
+        let page = read_body(
+            &url,
+            request.send_form(&[("level", &level), ("answer", answer)]),
+        )?;
+        let response = Response::parse(&page).ok_or_else(|| Error::Http {
+            url: url.clone(),
+            reason: "couldn't find a verdict in the response".to_owned(),
+        })?;
+        history.record(day, part, answer, response.clone());
+        Ok(Submission::Sent(response))
+    }
+}
+
+/// The body of a successful response; anything else is an error.
+fn read_body(
+    url: &str,
+    response: std::result::Result<ureq::Response, ureq::Error>,
+) -> Result<String> {
+    let http_error = |reason: String| Error::Http {
+        url: url.to_owned(),
+        reason,
+    };
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e: io::Error| http_error(e.to_string())),
+        Err(ureq::Error::Status(code, response)) => {
+            // The server explains itself in the first line, e.g. that the session has expired.
+            let text = response.into_string().unwrap_or_default();
+            let explanation = text.lines().next().unwrap_or_default().trim();
+            Err(http_error(
+                format!("{} {}", code, explanation).trim().to_owned(),
+            ))
+        }
+        Err(e) => Err(http_error(e.to_string())),
+    }
+}
+
+/// A tiny HTTP server on a local port which answers requests however the test likes.
+#[cfg(test)]
+pub(crate) mod stand_in {
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// A request as the stand-in server saw it.
+    #[derive(Debug, Clone, PartialEq, Eq)]
+    pub struct Seen {
+        pub method: String,
+        pub path: String,
+        pub cookie: Option<String>,
+        pub body: String,
+    }
+
+    /// Start serving with `respond`, which gives each request a status and a body. Returns the
+    /// server's base URL and a log of the requests it has seen.
+    pub fn serve(
+        respond: impl Fn(&Seen) -> (u16, String) + Send + 'static,
+    ) -> (String, Arc<Mutex<Vec<Seen>>>) {
+        let server = tiny_http::Server::http("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", server.server_addr().to_ip().unwrap());
+        let log = Arc::new(Mutex::new(Vec::new()));
+        let seen = Arc::clone(&log);
+        thread::spawn(move || {
+            for mut request in server.incoming_requests() {
+                let mut body = String::new();
+                request.as_reader().read_to_string(&mut body).unwrap();
+                let cookie = request
+                    .headers()
+                    .iter()
+                    .find(|h| h.field.equiv("Cookie"))
+                    .map(|h| h.value.to_string());
+                let request_seen = Seen {
+                    method: request.method().to_string(),
+                    path: request.url().to_owned(),
+                    cookie,
+                    body,
+                };
+                let (status, body) = respond(&request_seen);
+                seen.lock().unwrap().push(request_seen);
+                let response = tiny_http::Response::from_string(body).with_status_code(status);
+                let _ = request.respond(response);
+            }
+        });
+        (url, log)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fetch_and_cache() {
+        let (url, seen) = stand_in::serve(|request| match request.cookie.as_deref() {
+            Some("session=let-me-in") => (200, format!("input for {}\n", request.path)),
+            _ => (
+                400,
+                "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                    .to_owned(),
+            ),
+        });
+        let dir = env::temp_dir().join("aoc-2020-fetch-test");
+        let _ = fs::remove_dir_all(&dir);
+        let path = dir.join("input").join("day7.txt");
+
+        let client = Client::new(&format!("{}/", url), "let-me-in").unwrap();
+        assert_eq!(
+            client.fetch_input(7, &path).unwrap(),
+            Fetched::Downloaded { bytes: 23 }
+        );
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "input for /day/7/input\n"
+        );
+        assert_eq!(client.fetch_input(7, &path).unwrap(), Fetched::Cached);
+        assert_eq!(seen.lock().unwrap().len(), 1);
+
+        let stranger = Client::new(&url, "who-knows").unwrap();
+        let other = dir.join("input").join("day8.txt");
+        let e = stranger.fetch_input(8, &other).unwrap_err();
+        assert_eq!(
+            e.to_string(),
+            format!(
+                "couldn't fetch {}/day/8/input: 400 Puzzle inputs differ by user.  \
+                Please log in to get your puzzle input.",
+                url
+            )
+        );
+        assert!(!other.exists());
+        assert_eq!(seen.lock().unwrap()[1].method, "GET");
+
+        assert!(matches!(Client::new(&url, " "), Err(Error::NoSession)));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn submit_and_remember() {
+        let page = |text: &str| format!("<article><p>{}</p></article>", text);
+        let (url, seen) = stand_in::serve(move |request| {
+            let text = match request.body.as_str() {
+                "level=1&answer=100" => "That's not the right answer; your answer is too high.",
+                "level=1&answer=7" => {
+                    "You gave an answer too recently.  You have 1m 5s left to wait."
+                }
+                "level=1&answer=42" => "That's the right answer!",
+                "level=2&answer=42" => "You don't seem to be solving the right level.",
+                "level=2&answer=oops" => "Something else entirely",
+                _ => "That's not the right answer.",
+            };
+            (200, page(text))
+        });
+        let client = Client::new(&url, "let-me-in").unwrap();
+        let mut history = History::default();
+        let mut submit = |part, answer| client.submit(&mut history, 3, part, answer).unwrap();
+
+        use Response::*;
+        use Submission::*;
+        assert_eq!(submit(Part::One, "100"), Sent(TooHigh));
+        assert_eq!(submit(Part::One, "100"), Known(TooHigh));
+        assert_eq!(submit(Part::One, "150"), Known(TooHigh));
+        let limited = RateLimited {
+            wait_secs: Some(65),
+        };
+        assert_eq!(submit(Part::One, "7"), Sent(limited.clone()));
+        assert_eq!(submit(Part::One, "7"), Sent(limited));
+        assert_eq!(submit(Part::One, " 42\n"), Sent(Right));
+        assert_eq!(submit(Part::One, "43"), Known(Wrong));
+        assert_eq!(submit(Part::Two, "42"), Sent(WrongLevel));
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 5);
+        assert_eq!(seen[0].method, "POST");
+        assert_eq!(seen[0].path, "/day/3/answer");
+        assert_eq!(seen[0].cookie.as_deref(), Some("session=let-me-in"));
+        drop(seen);
+        assert_eq!(history.attempts.len(), 5);
+        assert_eq!(history.attempts[3].answer, "42");
+
+        let e = client
+            .submit(&mut history, 3, Part::Two, "oops")
+            .unwrap_err();
+        assert!(e
+            .to_string()
+            .ends_with("couldn't find a verdict in the response"));
+        assert_eq!(history.attempts.len(), 5);
+    }
+}
@@ -0,0 +1,80 @@
+//! Day 1: Report Repair. Find the expense entries which sum to 2020.
+
+use crate::input::parse_lines;
+use crate::scan::number_line;
+use crate::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+/// Two entries which add up to `target`, if there are any.
+pub fn two_entries_summing_to(target: usize, entries: &HashSet<usize>) -> Option<[usize; 2]> {
+    for first in entries.iter().filter(|&&e| e < target) {
+        let second = target - first;
+        if entries.contains(&second) {
+            return Some([*first, second]);
+        }
+    }
+    None
+}
+
+/// Three entries which add up to `target`, if there are any.
+pub fn three_entries_summing_to(target: usize, entries: &HashSet<usize>) -> Option<[usize; 3]> {
+    for first in entries.iter().filter(|&&e| e < target) {
+        let subtarget = target - first;
+        if let Some([b, c]) = two_entries_summing_to(subtarget, entries) {
+            return Some([*first, b, c]);
+        }
+    }
+    None
+}
+
+/// The puzzle input is one entry per line.
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed = HashSet<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input, number_line)
+    }
+
+    fn part1(expenses: &Self::Parsed) -> Answer {
+        match two_entries_summing_to(2020, expenses) {
+            Some([a, b]) => (a * b).into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn part2(expenses: &Self::Parsed) -> Answer {
+        match three_entries_summing_to(2020, expenses) {
+            Some([a, b, c]) => (a * b * c).into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn example_expense_entries() {
+        let expenses: [usize; 6] = [1721, 979, 366, 299, 675, 1456];
+        let expenses: HashSet<usize> = expenses.iter().cloned().collect();
+
+        let mut answer1 = two_entries_summing_to(2020, &expenses).unwrap();
+        answer1.sort();
+        assert_eq!(answer1, [299, 1721]);
+
+        let mut answer2 = three_entries_summing_to(2020, &expenses).unwrap();
+        answer2.sort();
+        assert_eq!(answer2, [366, 675, 979]);
+    }
+
+    #[test]
+    fn no_matching_entries() {
+        let expenses = Day1::parse("1\n2\n3\n").unwrap();
+        assert_eq!(Day1::part1(&expenses), Answer::Unsolved);
+        assert_eq!(Day1::part2(&expenses), Answer::Unsolved);
+    }
+}
@@ -1,28 +1,51 @@
+//! Day 10: Adapter Array. Chain joltage adapters from the outlet to the device, and count the ways
+//! to do it.
+
 use std::collections::HashMap;
-use std::str::FromStr;
 
 use petgraph::graphmap::GraphMap;
 use petgraph::Directed;
 use petgraph::Direction::Incoming;
 
-type Graph = GraphMap<usize, (), Directed>;
+use crate::input::parse_lines;
+use crate::scan::number_line;
+use crate::{Answer, ParseError, Solution};
+
+/// An edge from each joltage to every joltage which can be plugged into it.
+pub type Graph = GraphMap<usize, (), Directed>;
 
-fn main() {
-    let mut numbers: Vec<_> = aoc_2020::problem_lines()
-        .map(|s| usize::from_str(&s).unwrap())
-        .collect();
+/// The adapters' joltages in order, with the outlet's 0 at the start and the device at the end.
+pub fn parse_joltages(input: &str) -> Result<Vec<usize>, ParseError> {
+    let mut numbers: Vec<_> = parse_lines(input, number_line)?;
     numbers.push(0);
-    numbers.sort();
+    numbers.sort_unstable();
     numbers.push(numbers.last().unwrap() + 3);
+    Ok(numbers)
+}
 
-    let differences = joltage_difference_distribution(&numbers);
-    println!("{}", differences[&1] * differences[&3]);
+/// The puzzle input is one adapter's joltage per line, which parses as [`parse_joltages`] does.
+pub struct Day10;
 
-    let g = joltage_graph(&numbers);
-    println!("{}", count_joltage_chains(&numbers, &g));
+impl Solution for Day10 {
+    type Parsed = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_joltages(input)
+    }
+
+    fn part1(numbers: &Self::Parsed) -> Answer {
+        let differences = joltage_difference_distribution(numbers);
+        (differences[&1] * differences[&3]).into()
+    }
+
+    fn part2(numbers: &Self::Parsed) -> Answer {
+        let g = joltage_graph(numbers);
+        count_joltage_chains(numbers, &g).into()
+    }
 }
 
-fn joltage_difference_distribution(joltages_sorted: &[usize]) -> HashMap<usize, usize> {
+/// How many times each difference occurs between neighbouring joltages.
+pub fn joltage_difference_distribution(joltages_sorted: &[usize]) -> HashMap<usize, usize> {
     let mut differences = HashMap::new();
     for window in joltages_sorted.windows(2) {
         match window {
@@ -33,7 +56,8 @@ fn joltage_difference_distribution(joltages_sorted: &[usize]) -> HashMap<usize,
     differences
 }
 
-fn joltage_graph(joltages_sorted: &[usize]) -> Graph {
+/// The graph of which adapters can plug into which, given the sorted joltages.
+pub fn joltage_graph(joltages_sorted: &[usize]) -> Graph {
     let mut g = Graph::new();
     for &input_joltage in joltages_sorted {
         for jump in &[1, 2, 3] {
@@ -47,7 +71,8 @@ fn joltage_graph(joltages_sorted: &[usize]) -> Graph {
     g
 }
 
-fn count_joltage_chains(joltages_sorted: &[usize], g: &Graph) -> usize {
+/// The number of ways to chain adapters from the outlet to the device.
+pub fn count_joltage_chains(joltages_sorted: &[usize], g: &Graph) -> usize {
     let target = joltages_sorted.last().unwrap();
 
     // We want to count the number of paths from 0 to `target` in this DAG.
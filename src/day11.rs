@@ -0,0 +1,248 @@
+//! Day 11: Seating System. Let people shuffle around the waiting area's seats until nobody wants
+//! to move any more.
+
+use std::fmt;
+use std::fmt::{Debug, Display, Formatter};
+
+use crate::grid::{Grid, ALL_DIRECTIONS};
+use crate::{Answer, ParseError, Solution};
+
+/// A square of the waiting area.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Tile {
+    Floor,
+    EmptySeat,
+    OccupiedSeat,
+}
+
+impl Tile {
+    /// How the tile is drawn in the puzzle.
+    pub fn char(&self) -> char {
+        match self {
+            Tile::Floor => '.',
+            Tile::EmptySeat => 'L',
+            Tile::OccupiedSeat => '#',
+        }
+    }
+    pub fn parse(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Self::Floor),
+            'L' => Some(Self::EmptySeat),
+            '#' => Some(Self::OccupiedSeat),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Tile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.char())
+    }
+}
+
+/// Where the seats are, and who's sitting in them.
+#[derive(PartialEq, Eq, Clone)]
+pub struct Layout {
+    grid: Grid<Tile>,
+}
+
+impl Layout {
+    /// Reads lines of `.`, `L` and `#`.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let grid = Grid::parse(input, "., L or #", Tile::parse)?;
+        Ok(Self { grid })
+    }
+
+    pub fn grid(&self) -> &Grid<Tile> {
+        &self.grid
+    }
+
+    /// One round of people sitting down and standing up. Someone sits in an empty seat if
+    /// `count_occupation` finds nobody around it, and leaves if it finds at least
+    /// `occupation_leave_threshold` people.
+    pub fn iterate(
+        &self,
+        count_occupation: fn(&Layout, isize, isize) -> u8,
+        occupation_leave_threshold: u8,
+    ) -> Self {
+        let grid = Grid::from_fn(self.grid.width(), self.grid.height(), |x, y| {
+            let occupied = count_occupation(self, x as isize, y as isize);
+            match self.grid[(x, y)] {
+                Tile::Floor => Tile::Floor,
+                Tile::EmptySeat => {
+                    if occupied == 0 {
+                        Tile::OccupiedSeat
+                    } else {
+                        Tile::EmptySeat
+                    }
+                }
+                Tile::OccupiedSeat => {
+                    if occupied >= occupation_leave_threshold {
+                        Tile::EmptySeat
+                    } else {
+                        Tile::OccupiedSeat
+                    }
+                }
+            }
+        });
+        Layout { grid }
+    }
+
+    /// The occupied seats next to `(x, y)`: part 1's way of counting.
+    pub fn adjacent_occupied_seats(&self, x: isize, y: isize) -> u8 {
+        self.grid
+            .neighbours8(x, y)
+            .filter(|&t| *t == Tile::OccupiedSeat)
+            .count() as u8
+    }
+
+    /// The occupied seats which can be seen from `(x, y)`, looking past the floor in all eight
+    /// directions: part 2's way of counting.
+    pub fn visible_occupied_seats(&self, x: isize, y: isize) -> u8 {
+        let occupied_visible_in = |&direction: &(isize, isize)| -> bool {
+            self.grid.ray(x, y, direction).find(|&t| *t != Tile::Floor) == Some(&Tile::OccupiedSeat)
+        };
+        ALL_DIRECTIONS
+            .iter()
+            .filter(|d| occupied_visible_in(d))
+            .count() as u8
+    }
+
+    pub fn count(&self, t: Tile) -> usize {
+        self.grid.iter().filter(|tile| **tile == t).count()
+    }
+}
+
+impl Debug for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.grid, f)
+    }
+}
+
+impl Display for Layout {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        Display::fmt(&self.grid, f)
+    }
+}
+
+/// Every layout from `layout` up to the steady state, each once. Like [`find_steady_state`],
+/// this goes on for ever if the layout never settles.
+pub fn generations(
+    layout: &Layout,
+    count_occupation: fn(&Layout, isize, isize) -> u8,
+    occupation_leave_threshold: u8,
+) -> impl Iterator<Item = Layout> {
+    let mut next = Some(layout.clone());
+    std::iter::from_fn(move || {
+        let current = next.take()?;
+        let following = current.iterate(count_occupation, occupation_leave_threshold);
+        if following != current {
+            next = Some(following);
+        }
+        Some(current)
+    })
+}
+
+/// Iterates until a round changes nothing. This never returns if the layout keeps changing for
+/// ever, which puzzle inputs don't.
+pub fn find_steady_state(
+    layout: &Layout,
+    count_occupation: fn(&Layout, isize, isize) -> u8,
+    occupation_leave_threshold: u8,
+) -> Layout {
+    generations(layout, count_occupation, occupation_leave_threshold)
+        .last()
+        .unwrap()
+}
+
+/// The puzzle input is the layout of the waiting area.
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed = Layout;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Layout::parse(input)
+    }
+
+    fn part1(start: &Self::Parsed) -> Answer {
+        let steady = find_steady_state(start, Layout::adjacent_occupied_seats, 4);
+        steady.count(Tile::OccupiedSeat).into()
+    }
+
+    fn part2(start: &Self::Parsed) -> Answer {
+        let steady = find_steady_state(start, Layout::visible_occupied_seats, 5);
+        steady.count(Tile::OccupiedSeat).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    fn starting_layout() -> String {
+        examples::text(11, "example.txt")
+    }
+
+    #[test]
+    fn example_1() {
+        let start = Layout::parse(&starting_layout()).unwrap();
+        let steady = find_steady_state(&start, Layout::adjacent_occupied_seats, 4);
+        assert_eq!(steady.count(Tile::OccupiedSeat), 37);
+    }
+
+    #[test]
+    fn example_2() {
+        let start = Layout::parse(&starting_layout()).unwrap();
+
+        let step1 = Layout::parse(
+            "\
+#.##.##.##
+#######.##
+#.#.#..#..
+####.##.##
+#.##.##.##
+#.#####.##
+..#.#.....
+##########
+#.######.#
+#.#####.##",
+        )
+        .unwrap();
+        assert_eq!(step1, start.iterate(Layout::visible_occupied_seats, 5));
+        let step2 = Layout::parse(
+            "\
+#.LL.LL.L#
+#LLLLLL.LL
+L.L.L..L..
+LLLL.LL.LL
+L.LL.LL.LL
+L.LLLLL.LL
+..L.L.....
+LLLLLLLLL#
+#.LLLLLL.L
+#.LLLLL.L#",
+        )
+        .unwrap();
+        assert_eq!(step2, step1.iterate(Layout::visible_occupied_seats, 5));
+        let step3 = Layout::parse(
+            "\
+#.L#.##.L#
+#L#####.LL
+L.#.#..#..
+##L#.##.##
+#.##.#L.##
+#.#####.#L
+..#.#.....
+LLL####LL#
+#.L#####.L
+#.L####.L#",
+        )
+        .unwrap();
+        assert_eq!(step3, step2.iterate(Layout::visible_occupied_seats, 5));
+
+        let steady = find_steady_state(&start, Layout::visible_occupied_seats, 5);
+        assert_eq!(steady.count(Tile::OccupiedSeat), 26);
+    }
+}
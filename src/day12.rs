@@ -1,12 +1,22 @@
+//! Day 12: Rain Risk. Steer the ferry by following navigation instructions, read first as moving
+//! the ship and then as moving a waypoint.
+
 use num_enum::{IntoPrimitive, TryFromPrimitive};
 use std::convert::TryFrom;
 
-struct Action {
-    kind: ActionKind,
-    value: usize,
+use crate::input::parse_lines;
+use crate::scan::Scanner;
+use crate::{Answer, ParseError, Solution};
+
+/// A navigation instruction, like `F10`.
+pub struct Action {
+    pub kind: ActionKind,
+    /// A distance, or an angle in degrees for the rotations.
+    pub value: usize,
 }
 
-enum ActionKind {
+/// What an action does: the compass points move, the rotations turn, and forward goes forward.
+pub enum ActionKind {
     North,
     East,
     South,
@@ -28,27 +38,34 @@ impl From<Direction> for ActionKind {
 }
 
 impl Action {
-    fn parse(input: &str) -> Self {
-        let (first, rest) = input.split_at(1);
+    /// Reads a letter followed by a number. Angles must be multiples of 90 degrees.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut s = Scanner::new(input);
         use ActionKind::*;
-        let kind = match first {
-            "F" => Forward,
-            "L" => RotateLeft,
-            "R" => RotateRight,
-            "N" => North,
-            "E" => East,
-            "S" => South,
-            "W" => West,
-            _ => unimplemented!(),
+        let kind = match s.peek() {
+            Some('F') => Forward,
+            Some('L') => RotateLeft,
+            Some('R') => RotateRight,
+            Some('N') => North,
+            Some('E') => East,
+            Some('S') => South,
+            Some('W') => West,
+            _ => return Err(s.error("one of N, S, E, W, L, R or F")),
         };
-        let value = usize::from_str_radix(rest, 10).unwrap();
-        Self { kind, value }
+        s.char()?;
+        let number = s.clone();
+        let value = s.number()?;
+        if matches!(kind, RotateLeft | RotateRight) && (value % 90 != 0 || value > 360) {
+            return Err(number.error("a multiple of 90 degrees, up to 360"));
+        }
+        s.end()?;
+        Ok(Self { kind, value })
     }
 }
 
 #[derive(IntoPrimitive, TryFromPrimitive, Clone, Copy, PartialEq, Eq, Debug)]
 #[repr(i8)]
-enum Direction {
+pub enum Direction {
     North = 0,
     East = 1,
     South = 2,
@@ -67,20 +84,23 @@ fn rotate_right(d: Direction, angle: usize) -> Direction {
     Direction::try_from(value.rem_euclid(4)).unwrap()
 }
 
-struct Ship {
-    dir: Direction,
-    position: (isize, isize),
+/// The ship as part 1 sees it: the instructions move the ship itself.
+pub struct Ship {
+    pub dir: Direction,
+    /// East and north of where it started.
+    pub position: (isize, isize),
 }
 
 impl Ship {
-    fn new() -> Self {
+    /// A ship at the origin, facing east.
+    pub fn new() -> Self {
         Self {
             dir: Direction::East,
             position: (0, 0),
         }
     }
 
-    fn act(&mut self, action: &Action) {
+    pub fn act(&mut self, action: &Action) {
         use ActionKind::*;
         match action.kind {
             North => self.position.1 += action.value as isize,
@@ -97,20 +117,30 @@ impl Ship {
     }
 }
 
-struct ShipWithWaypoint {
-    position: (isize, isize),
-    waypoint: (isize, isize),
+impl Default for Ship {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The ship as part 2 sees it: most instructions move a waypoint, and the ship moves towards it.
+pub struct ShipWithWaypoint {
+    /// East and north of where it started.
+    pub position: (isize, isize),
+    /// East and north of the ship.
+    pub waypoint: (isize, isize),
 }
 
 impl ShipWithWaypoint {
-    fn new() -> Self {
+    /// A ship at the origin, with its waypoint 10 east and 1 north.
+    pub fn new() -> Self {
         Self {
             position: (0, 0),
             waypoint: (10, 1),
         }
     }
 
-    fn act(&mut self, action: &Action) {
+    pub fn act(&mut self, action: &Action) {
         use ActionKind::*;
         match action.kind {
             North => self.waypoint.1 += action.value as isize,
@@ -149,20 +179,40 @@ impl ShipWithWaypoint {
     }
 }
 
-fn main() {
-    let mut ship1 = Ship::new();
-    let mut ship2 = ShipWithWaypoint::new();
+impl Default for ShipWithWaypoint {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// The puzzle input is one action per line.
+pub struct Day12;
 
-    for line in aoc_2020::problem_lines() {
-        let a = Action::parse(&line);
-        ship1.act(&a);
-        ship2.act(&a);
+impl Solution for Day12 {
+    type Parsed = Vec<Action>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input, Action::parse)
+    }
+
+    fn part1(actions: &Self::Parsed) -> Answer {
+        let mut ship = Ship::new();
+        for action in actions {
+            ship.act(action);
+        }
+        manhattan_distance((0, 0), ship.position).into()
+    }
+
+    fn part2(actions: &Self::Parsed) -> Answer {
+        let mut ship = ShipWithWaypoint::new();
+        for action in actions {
+            ship.act(action);
+        }
+        manhattan_distance((0, 0), ship.position).into()
     }
-    println!("{}", manhattan_distance((0, 0), ship1.position));
-    println!("{}", manhattan_distance((0, 0), ship2.position));
 }
 
-fn manhattan_distance(src: (isize, isize), dest: (isize, isize)) -> usize {
+pub fn manhattan_distance(src: (isize, isize), dest: (isize, isize)) -> usize {
     let d = (dest.0 - src.0).abs() + (dest.1 - src.1).abs();
     d as usize
 }
@@ -174,16 +224,16 @@ mod test {
     #[test]
     fn example_1() {
         let mut ship = Ship::new();
-        ship.act(&Action::parse("F10"));
+        ship.act(&Action::parse("F10").unwrap());
         assert_eq!(ship.position, (10, 0));
-        ship.act(&Action::parse("N3"));
+        ship.act(&Action::parse("N3").unwrap());
         assert_eq!(ship.position, (10, 3));
-        ship.act(&Action::parse("F7"));
+        ship.act(&Action::parse("F7").unwrap());
         assert_eq!(ship.position, (17, 3));
-        ship.act(&Action::parse("R90"));
+        ship.act(&Action::parse("R90").unwrap());
         assert_eq!(ship.position, (17, 3));
         assert_eq!(ship.dir, Direction::South);
-        ship.act(&Action::parse("F11"));
+        ship.act(&Action::parse("F11").unwrap());
         assert_eq!(ship.position, (17, -8));
         assert_eq!(manhattan_distance((0, 0), ship.position), 25);
     }
@@ -191,19 +241,19 @@ mod test {
     #[test]
     fn example_2() {
         let mut ship = ShipWithWaypoint::new();
-        ship.act(&Action::parse("F10"));
+        ship.act(&Action::parse("F10").unwrap());
         assert_eq!(ship.position, (100, 10));
         assert_eq!(ship.waypoint, (10, 1));
-        ship.act(&Action::parse("N3"));
+        ship.act(&Action::parse("N3").unwrap());
         assert_eq!(ship.position, (100, 10));
         assert_eq!(ship.waypoint, (10, 4));
-        ship.act(&Action::parse("F7"));
+        ship.act(&Action::parse("F7").unwrap());
         assert_eq!(ship.position, (170, 38));
         assert_eq!(ship.waypoint, (10, 4));
-        ship.act(&Action::parse("R90"));
+        ship.act(&Action::parse("R90").unwrap());
         assert_eq!(ship.position, (170, 38));
         assert_eq!(ship.waypoint, (4, -10));
-        ship.act(&Action::parse("F11"));
+        ship.act(&Action::parse("F11").unwrap());
         assert_eq!(ship.position, (214, -72));
         assert_eq!(ship.waypoint, (4, -10));
         assert_eq!(manhattan_distance((0, 0), ship.position), 286);
@@ -0,0 +1,307 @@
+//! Day 13: Shuttle Search. Find the first bus to the airport, and then the first time at which the
+//! busses leave one minute after another. The second part is the Chinese remainder theorem, solved
+//! one bus at a time with the extended Euclidean algorithm.
+
+use crate::scan::{number_line, Scanner};
+use crate::{Answer, ParseError, Solution};
+
+/// The notes: when we get to the bus stop, and the timetable of bus ids, with `None` for each
+/// gap.
+pub struct Notes {
+    pub depart_after: u128,
+    pub ids: Vec<Option<u128>>,
+}
+
+impl Notes {
+    /// Reads a line with the earliest departure time, then a line of bus ids.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut lines = input.lines();
+        let first = lines.next().unwrap_or_default();
+        let depart_after = number_line(first).map_err(|e| e.within(input, first))?;
+        let second = lines
+            .next()
+            .ok_or_else(|| ParseError::end(input, "a list of bus ids"))?;
+        let ids = parse_ids(second).map_err(|e| e.within(input, second))?;
+        if ids.iter().all(Option::is_none) {
+            return Err(ParseError::at(input, second, "at least one bus id"));
+        }
+        Ok(Self { depart_after, ids })
+    }
+
+    /// The ids of the busses in service.
+    pub fn busses(&self) -> impl Iterator<Item = u128> + '_ {
+        self.ids.iter().flatten().copied()
+    }
+
+    /// The id of the first bus we can catch, and when it leaves.
+    pub fn earliest_bus(&self) -> (u128, u128) {
+        let get_time = |id| -> u128 {
+            let div = self.depart_after / id;
+            let rem = self.depart_after % id;
+            if rem != 0 {
+                (div + 1) * id
+            } else {
+                self.depart_after
+            }
+        };
+        self.busses()
+            .map(|id| (id, get_time(id)))
+            .min_by_key(|(_, time)| *time)
+            .unwrap()
+    }
+}
+
+/// The greatest common divisor `d` of `a` and `b`, with `x` and `y` such that `ax + by = d`.
+pub fn extended_gcd(a: i128, b: i128) -> (u128, i128, i128) {
+    let (d, mut u, mut v) = extended_gcd_positive(a.unsigned_abs(), b.unsigned_abs());
+    if a < 0 {
+        u *= -1;
+    }
+    if b < 0 {
+        v *= -1;
+    }
+    (d, u, v)
+}
+
+fn extended_gcd_positive(a: u128, b: u128) -> (u128, i128, i128) {
+    // From this exposition of the extended gcd algorithm http://anh.cs.luc.edu/331/notes/xgcd.pdf>.
+    // Computes d = gcd(a, b) and returns a triple (d, x, y) where d = ax + by.
+    let (mut last_x, mut x) = (1i128, 0i128);
+    let (mut last_y, mut y) = (0i128, 1i128);
+
+    let (mut last_remainder, mut remainder) = (a, b);
+
+    while remainder > 0 {
+        let quotient = last_remainder.div_euclid(remainder) as i128;
+        let oldx = x;
+        x = last_x - quotient * x;
+        last_x = oldx;
+
+        let oldy = y;
+        y = last_y - quotient * y;
+        last_y = oldy;
+
+        let oldremainder = remainder;
+        remainder = last_remainder.rem_euclid(remainder);
+        last_remainder = oldremainder;
+    }
+    (last_remainder, last_x, last_y)
+}
+
+/// The integers of the form `k * step + start`, for any integer `k`.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IncreasingLinearSubsequence {
+    // Set of integers of the form k*step + offset, for some integer k
+    start: u128,
+    step: u128,
+}
+
+impl IncreasingLinearSubsequence {
+    /// The sequence through `start` going up in `step`s. `step` mustn't be zero, but its sign
+    /// doesn't matter.
+    pub fn new(start: i128, step: i128) -> Self {
+        // normalise to a unique representation
+        Self {
+            start: start.rem_euclid(step) as u128,
+            step: step.unsigned_abs(),
+        }
+    }
+
+    /// The smallest member which isn't negative.
+    pub fn start(&self) -> u128 {
+        self.start
+    }
+
+    pub fn step(&self) -> u128 {
+        self.step
+    }
+
+    pub fn contains(&self, n: i128) -> bool {
+        n.rem_euclid(self.step as i128) as u128 == self.start
+    }
+
+    /// The numbers in both sequences, if there are any.
+    pub fn intersect(&self, other: &Self) -> Option<Self> {
+        // Solve self.step * x + self.offset == other.step * y + other.offset for (x, y)
+        // Rearrange to get self.step*x - other.step * y == other.offset - self.offset
+        let a = self.step as i128;
+        let b = -(other.step as i128);
+        let c = (other.start as i128) - (self.start as i128);
+        let line = solve_linear_diophantine(a, b, c)?;
+        Some(Self {
+            start: self.start + self.step * line.x.start,
+            step: num::integer::lcm(self.step, other.step),
+        })
+    }
+}
+
+/// The solutions of `ax + by = c`.
+pub struct BezoutSolution {
+    /// Every `x` which is part of a solution.
+    pub x: IncreasingLinearSubsequence,
+    _y: (i128, i128), // TODO: better to have decreasing linear sequence here
+}
+
+/// Solves `ax + by = c` for integers `x` and `y`, if it can be solved.
+pub fn solve_linear_diophantine(a: i128, b: i128, c: i128) -> Option<BezoutSolution> {
+    // The equation ax + by = c is a linear diophantine equation (where a, b, c are known
+    // integers and x and y are integer variables).
+    // This has a solution if and only if c is a multiple of the greatest common divisor of a and b.
+    let (d, u, v) = extended_gcd(a, b);
+    let d = d as i128;
+    // (We divide through both sides by d.)
+    // u and v chosen such that au + bv = d
+    if c % d != 0 {
+        return None;
+    }
+    // scale up to our exisiting equation
+    let scale_factor = c / d;
+
+    trace!(
+        "bezout coefficients {} {}, scaled by {}",
+        u,
+        v,
+        scale_factor
+    );
+    let (x, y) = (u * scale_factor, v * scale_factor);
+    // Moreover, if (x, y) is a solution, then the other solutions have the form (x + kv, y −
+    // ku), where k is an arbitrary integer, and u and v are the quotients of a and b
+    // (respectively) by the greatest common divisor of a and b.
+    Some(BezoutSolution {
+        x: IncreasingLinearSubsequence::new(x, b / d),
+        _y: (y, -a / d),
+    })
+}
+
+/// Bus ids separated by commas, with an `x` for each gap in the timetable. An id is how often its
+/// bus comes, so it can't be zero.
+pub fn parse_ids(input: &str) -> Result<Vec<Option<u128>>, ParseError> {
+    let mut s = Scanner::new(input);
+    let ids = s.separated(",", |s| {
+        if s.literal("x").is_ok() {
+            return Ok(None);
+        }
+        let start = s.clone();
+        match s.number().map_err(|e| e.expecting("a bus id or x"))? {
+            0 => Err(start.error("a bus id other than 0")),
+            id => Ok(Some(id)),
+        }
+    })?;
+    s.end()?;
+    Ok(ids)
+}
+
+/// The first time at which each bus leaves as many minutes later as its position in `ids`. Busses
+/// whose ids share a factor may never line up, in which case there's no such time; the puzzle's
+/// ids are all prime.
+pub fn string_of_departures(ids: &[Option<u128>]) -> Option<u128> {
+    let mut departures = IncreasingLinearSubsequence { start: 0, step: 1 };
+    for (index, &entry) in ids.iter().enumerate() {
+        match entry {
+            None => {}
+            Some(id) => {
+                departures = departures.intersect(&IncreasingLinearSubsequence::new(
+                    -(index as i128),
+                    id as i128,
+                ))?;
+            }
+        }
+    }
+    Some(departures.start)
+}
+
+/// The puzzle input is the notes.
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed = Notes;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Notes::parse(input)
+    }
+
+    fn part1(notes: &Self::Parsed) -> Answer {
+        let (id, time) = notes.earliest_bus();
+        (id * (time - notes.depart_after)).into()
+    }
+
+    fn part2(notes: &Self::Parsed) -> Answer {
+        match string_of_departures(&notes.ids) {
+            Some(time) => time.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generate::Prng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn test_gcd() {
+        assert_eq!(extended_gcd(14, 33), (1, -7, 3));
+        assert_eq!(extended_gcd(12, 32), (4, 3, -1));
+    }
+
+    #[test]
+    fn example_1() {
+        let input = "939\n7,13,x,x,59,x,31,19";
+        let notes = Notes::parse(input).unwrap();
+        assert_eq!(notes.earliest_bus(), (59, 944));
+    }
+
+    #[test]
+    fn example_2() {
+        assert_eq!(
+            string_of_departures(&parse_ids("7,13,x,x,59,x,31,19").unwrap()),
+            Some(1068781)
+        );
+        assert_eq!(
+            string_of_departures(&parse_ids("17,x,13,19").unwrap()),
+            Some(3417)
+        );
+        assert_eq!(
+            string_of_departures(&parse_ids("1789,37,47,1889").unwrap()),
+            Some(1202161486)
+        );
+    }
+
+    #[test]
+    fn intersection_is_commutative() {
+        let mut rng = Prng::seed_from_u64(13);
+        for _ in 0..1000 {
+            // Both sequences contain `common`, so they always intersect.
+            let common: i128 = rng.gen_range(-10_000..10_000);
+            let a = IncreasingLinearSubsequence::new(common, rng.gen_range(1..100));
+            let b = IncreasingLinearSubsequence::new(common, rng.gen_range(1..100));
+            let ab = a.intersect(&b).unwrap();
+            assert_eq!(Some(&ab), b.intersect(&a).as_ref());
+            assert!(ab.start < ab.step);
+            assert!(a.contains(ab.start as i128));
+            assert!(b.contains(ab.start as i128));
+        }
+        let evens = IncreasingLinearSubsequence::new(0, 2);
+        assert_eq!(
+            evens.intersect(&IncreasingLinearSubsequence::new(1, 4)),
+            None
+        );
+    }
+
+    #[test]
+    fn busses_which_never_line_up() {
+        // Both leave on even minutes, but they'd need to leave a minute apart.
+        assert_eq!(string_of_departures(&parse_ids("2,2").unwrap()), None);
+        let notes = Day13::parse("939\n2,2\n").unwrap();
+        assert_eq!(Day13::part2(&notes), Answer::Unsolved);
+    }
+
+    #[test]
+    fn bus_zero() {
+        let error = Day13::parse("939\n7,x,0,19\n").err().unwrap();
+        assert_eq!((error.line, error.column), (2, 5));
+        assert_eq!(error.expected, "a bus id other than 0");
+        assert!(Day13::parse("939\nx,x\n").is_err());
+    }
+}
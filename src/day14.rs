@@ -0,0 +1,271 @@
+//! Day 14: Docking Data. Run the sea port's initialization program, which writes to memory through
+//! a bitmask; in version 2 of the decoder chip the mask applies to addresses and floats some bits.
+
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+use num_enum::TryFromPrimitive;
+
+use crate::input::parse_lines;
+use crate::scan::Scanner;
+use crate::{Answer, ParseError, Solution};
+
+/// A character of a mask. The discriminants are the characters.
+#[derive(Clone, Copy, TryFromPrimitive, Debug, PartialEq, Eq)]
+#[repr(u8)]
+pub enum MaskBit {
+    Zero = b'0',
+    One = b'1',
+    Unset = b'X',
+}
+
+/// A mask, most significant bit first.
+pub type Mask = [MaskBit; 36];
+
+/// Version 1: sets and clears bits of a value, and leaves the `X` bits alone.
+pub fn apply_mask(mask: &Mask, value: &u64) -> u64 {
+    let mut output = *value;
+    for (i, mask_bit) in mask.iter().enumerate() {
+        let bit = 1 << (35 - i);
+        match mask_bit {
+            MaskBit::Zero => output &= !bit,
+            MaskBit::One => output |= bit,
+            MaskBit::Unset => (),
+        }
+    }
+    output
+}
+
+/// Version 2: sets the `1` bits of an address, then gives every address made by setting the `X`
+/// bits each way: `2^k` of them for a mask with `k` `X`s.
+pub fn get_addrs(mask: &Mask, addr: &u64) -> Vec<u64> {
+    // First pass to apply all the 1 bits
+    let mut base_addr = *addr;
+    for (i, &mask_bit) in mask.iter().enumerate() {
+        if mask_bit == MaskBit::One {
+            let bit = 1 << (35 - i);
+            base_addr |= bit;
+        }
+    }
+
+    // Second pass to apply all the floating bits
+    let mut addrs = vec![base_addr];
+    for (i, mask_bit) in mask.iter().enumerate() {
+        let bit = 1 << (35 - i);
+        match mask_bit {
+            MaskBit::Zero | MaskBit::One => (),
+            MaskBit::Unset => {
+                for j in 0..addrs.len() {
+                    addrs[j] &= !bit;
+                    addrs.push(addrs[j] | bit);
+                }
+            }
+        }
+    }
+    addrs
+}
+
+/// The docking program's computer: a mask, and memory which starts out all zeroes.
+pub struct Computer {
+    mask: Mask,
+    memory: HashMap<u64, u64>,
+}
+
+impl Computer {
+    /// A computer whose mask changes nothing.
+    pub fn new() -> Self {
+        Self {
+            mask: [MaskBit::Unset; 36],
+            memory: HashMap::new(),
+        }
+    }
+
+    /// The values in memory, by address. Addresses never written to are left out.
+    pub fn memory(&self) -> &HashMap<u64, u64> {
+        &self.memory
+    }
+
+    pub fn execute_v1(&mut self, ins: &Instruction) {
+        match ins {
+            Instruction::SetMask(m) => self.mask = *m,
+            Instruction::WriteMem(addr, value) => {
+                self.memory.insert(*addr, apply_mask(&self.mask, value));
+            }
+        }
+    }
+
+    pub fn execute_v2(&mut self, ins: &Instruction) {
+        match ins {
+            Instruction::SetMask(m) => self.mask = *m,
+            Instruction::WriteMem(addr, value) => {
+                for addr in get_addrs(&self.mask, addr) {
+                    self.memory.insert(addr, *value);
+                }
+            }
+        }
+    }
+}
+
+impl Default for Computer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A line of the initialization program.
+pub enum Instruction {
+    SetMask(Mask),
+    /// An address, then a value.
+    WriteMem(u64, u64),
+}
+
+impl Instruction {
+    /// Reads a line like `mask = 0X1...` or `mem[8] = 11`.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut s = Scanner::new(input);
+        if s.literal("mask = ").is_ok() {
+            let mask = s.rest();
+            return parse_mask(mask)
+                .map(Self::SetMask)
+                .map_err(|e| e.within(input, mask));
+        }
+        s.literal("mem[")
+            .map_err(|e| e.expecting("\"mask = \" or \"mem[\""))?;
+        let addr = s.number()?;
+        s.literal("] = ")?;
+        let value = s.number()?;
+        s.end()?;
+        Ok(Self::WriteMem(addr, value))
+    }
+}
+
+/// 36 of 0, 1 or X, most significant bit first.
+pub fn parse_mask(input: &str) -> Result<Mask, ParseError> {
+    let mut mask = [MaskBit::Unset; 36];
+    let mut chars = input.char_indices();
+    for bit in mask.iter_mut() {
+        let (i, c) = chars
+            .next()
+            .ok_or_else(|| ParseError::end(input, "0, 1 or X"))?;
+        *bit = u8::try_from(c)
+            .ok()
+            .and_then(|b| MaskBit::try_from(b).ok())
+            .ok_or_else(|| ParseError::at(input, &input[i..i + c.len_utf8()], "0, 1 or X"))?;
+    }
+    match chars.next() {
+        None => Ok(mask),
+        Some((i, _)) => Err(ParseError::at(input, &input[i..], "the end of the line")),
+    }
+}
+
+/// The sum of everything left in memory after running `program`, executing each instruction with
+/// `exec`.
+pub fn run_program(program: &[Instruction], exec: fn(&mut Computer, &Instruction)) -> usize {
+    let mut computer = Computer::new();
+    for instruction in program {
+        exec(&mut computer, instruction);
+    }
+    computer.memory.values().map(|&x| x as usize).sum()
+}
+
+/// The puzzle input is the initialization program, one instruction per line.
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed = Vec<Instruction>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input, Instruction::parse)
+    }
+
+    fn part1(program: &Self::Parsed) -> Answer {
+        run_program(program, Computer::execute_v1).into()
+    }
+
+    fn part2(program: &Self::Parsed) -> Answer {
+        run_program(program, Computer::execute_v2).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generate::Prng;
+    use rand::{Rng, SeedableRng};
+
+    #[test]
+    fn example_1() {
+        let input = "\
+mask = XXXXXXXXXXXXXXXXXXXXXXXXXXXXX1XXXX0X
+mem[8] = 11
+mem[7] = 101
+mem[8] = 0";
+        let mut computer = Computer::new();
+        let instructions: Vec<_> = input
+            .split("\n")
+            .map(|line| Instruction::parse(line).unwrap())
+            .collect();
+
+        computer.execute_v1(&instructions[0]);
+        computer.execute_v1(&instructions[1]);
+        assert_eq!(computer.memory.get(&8u64), Some(&73u64));
+        computer.execute_v1(&instructions[2]);
+        assert_eq!(computer.memory.get(&7u64), Some(&101u64));
+        computer.execute_v1(&instructions[3]);
+        assert_eq!(computer.memory.get(&8u64), Some(&64u64));
+    }
+
+    #[test]
+    fn example_2() {
+        let input = "\
+mask = 000000000000000000000000000000X1001X
+mem[42] = 100
+mask = 00000000000000000000000000000000X0XX
+mem[26] = 1";
+        let mut computer = Computer::new();
+        let instructions: Vec<_> = input
+            .split("\n")
+            .map(|line| Instruction::parse(line).unwrap())
+            .collect();
+
+        computer.execute_v2(&instructions[0]);
+        computer.execute_v2(&instructions[1]);
+        for k in &[26, 27, 58, 59] {
+            assert_eq!(computer.memory.get(k), Some(&100));
+        }
+    }
+
+    #[test]
+    fn floating_bits_give_every_address() {
+        let mut rng = Prng::seed_from_u64(14);
+        for _ in 0..300 {
+            let mut mask = [MaskBit::Zero; 36];
+            for bit in mask.iter_mut() {
+                *bit = match rng.gen_range(0..10) {
+                    0 => MaskBit::Unset,
+                    1..=4 => MaskBit::One,
+                    _ => MaskBit::Zero,
+                };
+            }
+            let addr = rng.gen_range(0..1 << 36);
+            let floating = mask.iter().filter(|&&b| b == MaskBit::Unset).count();
+
+            let addrs = get_addrs(&mask, &addr);
+            assert_eq!(addrs.len(), 1 << floating);
+            let distinct: std::collections::HashSet<_> = addrs.iter().collect();
+            assert_eq!(distinct.len(), addrs.len());
+            // Outside the floating bits, every address is the original with the ones set.
+            let bits_where = |wanted: MaskBit| -> u64 {
+                (0..36)
+                    .filter(|&i| mask[i] == wanted)
+                    .map(|i| 1 << (35 - i))
+                    .sum()
+            };
+            let fixed = !bits_where(MaskBit::Unset) & ((1 << 36) - 1);
+            let ones = bits_where(MaskBit::One);
+            for a in addrs {
+                assert_eq!(a & fixed, (addr | ones) & fixed);
+            }
+        }
+    }
+}
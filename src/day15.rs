@@ -0,0 +1,89 @@
+//! Day 15: Rambunctious Recitation. Play the elves' memory game, where each number spoken is how
+//! long ago the previous number was last spoken.
+
+use std::collections::HashMap;
+
+use crate::scan::Scanner;
+use crate::{Answer, ParseError, Solution};
+
+/// The game after the starting numbers, yielding each turn and the number spoken on it.
+pub struct MemoryGame {
+    turn: usize,
+    number: usize,
+    last_spoken_at: HashMap<usize, usize>,
+}
+
+impl MemoryGame {
+    /// The game which starts with `input`. None of the starting numbers may repeat.
+    pub fn new(input: &[usize]) -> Self {
+        let mut last_spoken_at = HashMap::new();
+        for (time, number) in input.iter().enumerate() {
+            last_spoken_at.insert(*number, time + 1);
+        }
+        Self {
+            turn: input.len() + 1,
+            number: 0,
+            last_spoken_at,
+        }
+    }
+}
+
+impl Iterator for MemoryGame {
+    type Item = (usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (this_turn, this_number) = (self.turn, self.number);
+        match self.last_spoken_at.get(&this_number) {
+            None => self.number = 0000000,
+            Some(t) => self.number = self.turn - t,
+        }
+        self.last_spoken_at.insert(this_number, this_turn);
+        self.turn += 1;
+        Some((this_turn, this_number))
+    }
+}
+
+/// The number spoken on the given turn, counting from 1. The turn must be after the starting
+/// numbers.
+pub fn spoken_on_turn(starting_numbers: &[usize], turn: usize) -> usize {
+    let mut game = MemoryGame::new(starting_numbers);
+    game.find(|&(t, _)| t == turn).unwrap().1
+}
+
+/// The puzzle input is the starting numbers, separated by commas.
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut s = Scanner::new(input.lines().next().unwrap_or_default());
+        let numbers = s.separated(",", Scanner::number)?;
+        s.end()?;
+        Ok(numbers)
+    }
+
+    fn part1(starting_numbers: &Self::Parsed) -> Answer {
+        spoken_on_turn(starting_numbers, 2020).into()
+    }
+
+    fn part2(starting_numbers: &Self::Parsed) -> Answer {
+        // The slowest of the lot; `aoc bench 15` says how slow. Is there a better way?
+        spoken_on_turn(starting_numbers, 30000000).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_1() {
+        let game = MemoryGame::new(&[0, 3, 6]);
+        let numbers: Vec<_> = game.take(7).collect();
+        assert_eq!(
+            numbers,
+            vec![(4, 0), (5, 3), (6, 3), (7, 1), (8, 0), (9, 4), (10, 0)]
+        );
+    }
+}
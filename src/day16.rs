@@ -1,78 +1,115 @@
-#[macro_use]
-extern crate scan_fmt;
+//! Day 16: Ticket Translation. Throw out the tickets with impossible values, then work out which
+//! field is which from the ones that are left.
 
 use array2d::Array2D;
 use bimap::BiMap;
 use itertools::{join, Itertools};
 use std::collections::{HashMap, VecDeque};
+use std::fmt::Write;
 use std::ops::RangeInclusive;
 
-type Rule = [RangeInclusive<usize>; 2];
-type Rules = HashMap<String, Rule>;
+use crate::input::records;
+use crate::scan::Scanner;
+use crate::{Answer, ParseError, Solution};
 
+/// A field's value must be in one of the two ranges.
+pub type Rule = [RangeInclusive<usize>; 2];
+/// The rule for each field, by name.
+pub type Rules = HashMap<String, Rule>;
+
+/// A ticket's values, in an order which is the same for every ticket but isn't written down.
 #[derive(Debug)]
-struct Ticket {
-    values: Vec<usize>,
+pub struct Ticket {
+    pub values: Vec<usize>,
 }
 
+/// Everything in the puzzle input.
 #[derive(Debug)]
-struct Notes {
-    rules: Rules,
-    my_ticket: Ticket,
-    nearby_tickets: Vec<Ticket>,
+pub struct Notes {
+    pub rules: Rules,
+    pub my_ticket: Ticket,
+    pub nearby_tickets: Vec<Ticket>,
 }
 
 impl Notes {
-    fn parse<'a, T>(input: T) -> Self
-    where
-        T: IntoIterator<Item = &'a str>,
-    {
-        let mut lines = input.into_iter();
-        let rules = Self::parse_rules(lines.by_ref().take_while(|&s| s != ""));
-
-        lines.find(|&s| s == "your ticket:");
-        let my_ticket = Self::parse_ticket(lines.next().unwrap());
-
-        lines.find(|&s| s == "nearby tickets:");
-        let nearby_tickets = lines.map(Self::parse_ticket).collect();
-        Self {
+    /// Reads the rules, `your ticket:` and `nearby tickets:`, with a blank line after each section.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut sections = records(input);
+        let mut next_section = |expected: &str| {
+            sections
+                .next()
+                .ok_or_else(|| ParseError::end(input, expected))
+        };
+        let parse_tickets = |lines: &[&str]| {
+            lines
+                .iter()
+                .map(|line| Self::parse_ticket(line).map_err(|e| e.within(input, line)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let rules = next_section("the rules")?
+            .iter()
+            .map(|line| Self::parse_rule(line).map_err(|e| e.within(input, line)))
+            .collect::<Result<_, _>>()?;
+
+        let mine = next_section("\"your ticket:\"")?;
+        let my_ticket = match after_header(input, &mine, "your ticket:")? {
+            [line] => Self::parse_ticket(line).map_err(|e| e.within(input, line))?,
+            [] => {
+                let end_of_header = &mine[0][mine[0].len()..];
+                return Err(ParseError::at(input, end_of_header, "a ticket"));
+            }
+            [_, extra, ..] => return Err(ParseError::at(input, extra, "a blank line")),
+        };
+
+        let nearby = next_section("\"nearby tickets:\"")?;
+        let nearby_tickets = parse_tickets(after_header(input, &nearby, "nearby tickets:")?)?;
+        Ok(Self {
             rules,
             my_ticket,
             nearby_tickets,
-        }
+        })
     }
 
-    fn parse_rules<'a, L>(lines: L) -> Rules
-    where
-        L: Iterator<Item = &'a str>,
-    {
-        let mut rules = HashMap::new();
-        for line in lines {
-            if let Ok((name, a, b, c, d)) = scan_fmt!(
-                line,
-                "{/[^:]+/}: {d}-{d} or {d}-{d}",
-                String,
-                usize,
-                usize,
-                usize,
-                usize
-            ) {
-                rules.insert(name, [a..=b, c..=d]);
-            }
-        }
-        rules
+    /// A field's name and its valid ranges, like `class: 1-3 or 5-7`.
+    fn parse_rule(line: &str) -> Result<(String, [RangeInclusive<usize>; 2]), ParseError> {
+        let mut s = Scanner::new(line);
+        let name = s.until(": ")?.to_owned();
+        s.literal(": ")?;
+        let range = |s: &mut Scanner| -> Result<_, ParseError> {
+            let start = s.number()?;
+            s.literal("-")?;
+            Ok(start..=s.number()?)
+        };
+        let first = range(&mut s)?;
+        s.literal(" or ")?;
+        let second = range(&mut s)?;
+        s.end()?;
+        Ok((name, [first, second]))
     }
 
-    fn parse_ticket(input: &str) -> Ticket {
-        let values = input
-            .split(",")
-            .map(|s| usize::from_str_radix(s, 10).unwrap())
-            .collect();
-        Ticket { values }
+    fn parse_ticket(input: &str) -> Result<Ticket, ParseError> {
+        let mut s = Scanner::new(input);
+        let values = s.separated(",", Scanner::number)?;
+        s.end()?;
+        Ok(Ticket { values })
     }
 }
 
-fn test_validity(ticket: &Ticket, rules: &Rules) -> Option<usize> {
+/// The lines of `section` after its first, which must be `header`.
+fn after_header<'a>(
+    input: &str,
+    section: &'a [&'a str],
+    header: &str,
+) -> Result<&'a [&'a str], ParseError> {
+    match section.split_first() {
+        Some((first, rest)) if *first == header => Ok(rest),
+        _ => Err(ParseError::at(input, section[0], format!("{:?}", header))),
+    }
+}
+
+/// A value on the ticket which no rule allows, if there is one.
+pub fn test_validity(ticket: &Ticket, rules: &Rules) -> Option<usize> {
     ticket
         .values
         .iter()
@@ -88,15 +125,15 @@ fn dump<'a>(
     possibilities: &Array2D<bool>,
     known: &BiMap<&str, usize>,
     rules: impl Iterator<Item = &'a str>,
-) {
+) -> String {
     let columns: Vec<usize> = (0..possibilities.num_columns())
         .filter(|i| !known.contains_right(i))
         .collect();
-    print!("\n                        ");
+    let mut out = String::from("\n                        ");
     for i in &columns {
-        print!(" {:2}", i);
+        write!(out, " {:2}", i).unwrap();
     }
-    println!();
+    out.push('\n');
     for (name_index, name) in rules.enumerate() {
         if known.contains_left(&name) {
             continue;
@@ -108,9 +145,10 @@ fn dump<'a>(
                 true => 't',
                 false => ' ',
             });
-        println!("{:25} {}", name, join(entries, "  "));
+        writeln!(out, "{:25} {}", name, join(entries, "  ")).unwrap();
     }
-    println!("Known: {:?}\n", known);
+    write!(out, "Known: {:?}", known).unwrap();
+    out
 }
 
 fn name_index_known_for_index(index: usize, possibilities: &Array2D<bool>) -> Option<usize> {
@@ -149,7 +187,9 @@ enum Investigate {
     Both,
 }
 
-fn deduce_indices<'a>(rules: &'a Rules, tickets: &[&Ticket]) -> BiMap<&'a str, usize> {
+/// Which field of a ticket each rule is for, by elimination, given tickets whose values are all
+/// allowed by some rule.
+pub fn deduce_indices<'a>(rules: &'a Rules, tickets: &[&Ticket]) -> BiMap<&'a str, usize> {
     // Track whether each (name, index) possibility is possible in a matrix.
     // To do so, fix an ordering of the rules.
     let rules = rules.iter().collect_vec();
@@ -161,17 +201,16 @@ fn deduce_indices<'a>(rules: &'a Rules, tickets: &[&Ticket]) -> BiMap<&'a str, u
 
     while known.len() < rules.len() {
         match recently_marked_false.pop_front() {
-            Some((name_index, index, Consider::Column)) => {
+            Some((_, index, Consider::Column)) => {
                 if let Some(other_name_index) = name_index_known_for_index(index, &possibilities) {
-                    println!(
+                    debug!(
                         "Looking at columns, now {} must be {}",
                         rules[other_name_index].0, index
                     );
-                    dump(
-                        &possibilities,
-                        &known,
-                        rules.iter().map(|(name, _)| name.as_str()),
-                    );
+                    if log_enabled!(log::Level::Trace) {
+                        let names = rules.iter().map(|(name, _)| name.as_str());
+                        trace!("{}", dump(&possibilities, &known, names));
+                    }
                     known.insert(rules[other_name_index].0.as_str(), index);
                     for other_index in (0..possibilities.num_columns()).filter(|&o| o != index) {
                         mark_false(
@@ -185,17 +224,16 @@ fn deduce_indices<'a>(rules: &'a Rules, tickets: &[&Ticket]) -> BiMap<&'a str, u
                     }
                 }
             }
-            Some((name_index, index, Consider::Row)) => {
+            Some((name_index, _, Consider::Row)) => {
                 if let Some(other_index) = index_known_for_name_index(name_index, &possibilities) {
-                    println!(
+                    debug!(
                         "Looking at rows, now {} must be {}",
                         rules[name_index].0, other_index
                     );
-                    dump(
-                        &possibilities,
-                        &known,
-                        rules.iter().map(|(name, _)| name.as_str()),
-                    );
+                    if log_enabled!(log::Level::Trace) {
+                        let names = rules.iter().map(|(name, _)| name.as_str());
+                        trace!("{}", dump(&possibilities, &known, names));
+                    }
                     known.insert(rules[name_index].0.as_str(), other_index);
                     for other_name_index in
                         (0..possibilities.num_rows()).filter(|&o| o != name_index)
@@ -226,7 +264,7 @@ fn deduce_indices<'a>(rules: &'a Rules, tickets: &[&Ticket]) -> BiMap<&'a str, u
                         if r1.contains(value) || r2.contains(value) {
                             continue;
                         }
-                        // print!("Since {} isn't in {:?} | {:?}, ", value, r1, r2);
+                        trace!("Since {} isn't in {:?} | {:?},", value, r1, r2);
                         mark_false(
                             &mut possibilities,
                             &mut recently_marked_false,
@@ -254,7 +292,7 @@ fn mark_false(
     investigate: Investigate,
 ) {
     if *possibilities.get(name_index, index).unwrap() {
-        // println!("{} can't be {}", name, index);
+        trace!("{} can't be {}", name, index);
         if investigate == Investigate::Row || investigate == Investigate::Both {
             recently_marked_false.push_back((name_index, index, Consider::Row));
         }
@@ -265,7 +303,11 @@ fn mark_false(
     }
 }
 
-fn filter_completely_invalid<'a>(rules: &Rules, tickets: &'a [Ticket]) -> (Vec<&'a Ticket>, usize) {
+/// The tickets whose values are all allowed by some rule, and the sum of the values which aren't.
+pub fn filter_completely_invalid<'a>(
+    rules: &Rules,
+    tickets: &'a [Ticket],
+) -> (Vec<&'a Ticket>, usize) {
     let mut sum = 0;
     let mut remaining = Vec::new();
     for ticket in tickets {
@@ -277,26 +319,39 @@ fn filter_completely_invalid<'a>(rules: &Rules, tickets: &'a [Ticket]) -> (Vec<&
     (remaining, sum)
 }
 
-fn main() {
-    let input = aoc_2020::problem_content();
-    let notes = Notes::parse(input.split("\n"));
-    let (remaining, sum_bad_entries) =
-        filter_completely_invalid(&notes.rules, &notes.nearby_tickets);
-    println!("{}", sum_bad_entries);
-    let mapping = deduce_indices(&notes.rules, &remaining);
-    let departure_prod = mapping
-        .iter()
-        .inspect(|(name, &i)| println!("{}, {}, {}", name, i, notes.my_ticket.values[i]))
-        .filter(|(name, _)| name.starts_with("departure"))
-        .map(|(_, &i)| notes.my_ticket.values[i])
-        .product::<usize>();
-    println!("{}", departure_prod);
-
-    for (name, [r1, r2]) in &notes.rules {
-        let &index = mapping.get_by_left(&name.as_str()).unwrap();
-        for ticket in &remaining {
-            assert!(r1.contains(&ticket.values[index]) || r2.contains(&ticket.values[index]));
+/// The puzzle input is the notes.
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed = Notes;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Notes::parse(input)
+    }
+
+    fn part1(notes: &Self::Parsed) -> Answer {
+        filter_completely_invalid(&notes.rules, &notes.nearby_tickets)
+            .1
+            .into()
+    }
+
+    fn part2(notes: &Self::Parsed) -> Answer {
+        let remaining = filter_completely_invalid(&notes.rules, &notes.nearby_tickets).0;
+        let mapping = deduce_indices(&notes.rules, &remaining);
+        let departure_prod = mapping
+            .iter()
+            .inspect(|(name, &i)| debug!("{}, {}, {}", name, i, notes.my_ticket.values[i]))
+            .filter(|(name, _)| name.starts_with("departure"))
+            .map(|(_, &i)| notes.my_ticket.values[i])
+            .product::<usize>();
+
+        for (name, [r1, r2]) in &notes.rules {
+            let &index = mapping.get_by_left(&name.as_str()).unwrap();
+            for ticket in &remaining {
+                assert!(r1.contains(&ticket.values[index]) || r2.contains(&ticket.values[index]));
+            }
         }
+        departure_prod.into()
     }
 }
 
@@ -319,7 +374,7 @@ nearby tickets:
 40,4,50
 55,2,20
 38,6,12";
-        let notes = Notes::parse(input.split("\n"));
+        let notes = Notes::parse(input).unwrap();
         let sum_bad_entries = filter_completely_invalid(&notes.rules, &notes.nearby_tickets).1;
         assert_eq!(sum_bad_entries, 71);
     }
@@ -338,7 +393,7 @@ nearby tickets:
 3,9,18
 15,1,5
 5,14,9";
-        let notes = Notes::parse(input.split("\n"));
+        let notes = Notes::parse(input).unwrap();
         let remaining = filter_completely_invalid(&notes.rules, &notes.nearby_tickets).0;
         let mapping = deduce_indices(&notes.rules, &remaining);
         assert_eq!(mapping.get_by_left(&"row"), Some(&0));
@@ -1,42 +1,46 @@
-#[macro_use]
-extern crate itertools;
+//! Day 17: Conway Cubes. Run Conway's game of life in three dimensions, then in four.
 
+use itertools::iproduct;
 use std::collections::HashMap;
 use std::fmt::{Display, Formatter, Write};
 use std::ops::Range;
 
-fn main() {
-    let lines: Vec<_> = aoc_2020::problem_lines().collect();
-    let lines = lines.iter().map(|s| s.as_str());
-    let orig_grid = new_grid(lines);
-    let mut grid = orig_grid.clone();
+use crate::grid;
+use crate::{Answer, ParseError, Solution};
 
-    for _ in 0..6 {
-        grid = iterate(&grid);
+/// The puzzle input is the starting slice, which parses as [`new_grid`] does.
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed = Grid;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        new_grid(input)
     }
-    println!(
-        "{}",
-        grid.spaces
-            .values()
-            .filter(|&&x| x == Space::Active)
-            .count()
-    );
 
-    let mut grid = Grid4::embed(&orig_grid);
-    for _ in 0..6 {
-        grid = iterate4(&grid);
+    fn part1(start: &Self::Parsed) -> Answer {
+        let mut grid = start.clone();
+        for _ in 0..CYCLES {
+            grid = iterate(&grid);
+        }
+        grid.active().into()
+    }
+
+    fn part2(start: &Self::Parsed) -> Answer {
+        let mut grid = Grid4::embed(start);
+        for _ in 0..CYCLES {
+            grid = iterate4(&grid);
+        }
+        grid.active().into()
     }
-    println!(
-        "{}",
-        grid.spaces
-            .values()
-            .filter(|&&x| x == Space::Active)
-            .count()
-    );
 }
 
+/// The number of cycles the boot process runs for.
+pub const CYCLES: usize = 6;
+
+/// A cube of the pocket dimension.
 #[derive(Copy, Clone, Eq, PartialEq)]
-enum Space {
+pub enum Space {
     Inactive,
     Active,
 }
@@ -50,8 +54,9 @@ impl Display for Space {
     }
 }
 
+/// A box of three-dimensional space. Everything outside it is inactive.
 #[derive(Clone)]
-struct Grid {
+pub struct Grid {
     // Use of a hashmap is icky, but it means I can ignore the problems with negative indices
     // without having to waste time transforming coordinages
     width: Range<isize>,
@@ -60,7 +65,8 @@ struct Grid {
     spaces: HashMap<(isize, isize, isize), Space>,
 }
 
-struct Grid4 {
+/// A box of four-dimensional space. Everything outside it is inactive.
+pub struct Grid4 {
     width: Range<isize>,
     height: Range<isize>,
     depth: Range<isize>,
@@ -69,7 +75,8 @@ struct Grid4 {
 }
 
 impl Grid4 {
-    fn embed(src: &Grid) -> Self {
+    /// The three-dimensional grid as the slice at `w = 0`.
+    pub fn embed(src: &Grid) -> Self {
         let mut spaces = HashMap::new();
         for (&(x, y, z), &space) in src.spaces.iter() {
             spaces.insert((x, y, z, 0), space);
@@ -82,20 +89,76 @@ impl Grid4 {
             spaces,
         }
     }
+
+    /// The number of active cubes.
+    pub fn active(&self) -> usize {
+        self.spaces
+            .values()
+            .filter(|&&x| x == Space::Active)
+            .count()
+    }
 }
 
 impl Grid {
-    fn dump(&self) {
+    /// The number of active cubes.
+    pub fn active(&self) -> usize {
+        self.spaces
+            .values()
+            .filter(|&&x| x == Space::Active)
+            .count()
+    }
+
+    /// The extent of the box along each axis.
+    pub fn bounds(&self) -> [Range<isize>; 3] {
+        [self.width.clone(), self.height.clone(), self.depth.clone()]
+    }
+
+    /// The cube at a point. Points outside the box are inactive.
+    pub fn get(&self, x: isize, y: isize, z: isize) -> Space {
+        self.spaces
+            .get(&(x, y, z))
+            .copied()
+            .unwrap_or(Space::Inactive)
+    }
+}
+
+/// Each slice of constant `z` in turn, as the puzzle draws them.
+impl Display for Grid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         for z in self.depth.clone() {
-            println!("z={}", z);
+            writeln!(f, "z={}", z)?;
             for y in self.height.clone() {
                 for x in self.width.clone() {
-                    print!("{}", self.spaces[&(x, y, z)]);
+                    write!(f, "{}", self.get(x, y, z))?;
                 }
-                println!();
+                writeln!(f)?;
+            }
+            if z + 1 != self.depth.end {
+                writeln!(f)?;
             }
-            println!();
         }
+        Ok(())
+    }
+}
+
+/// Each slice of constant `z` and `w` in turn, as the puzzle draws them.
+impl Display for Grid4 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let slices = iproduct!(self.fourth_dimension.clone(), self.depth.clone());
+        for (i, (w, z)) in slices.enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "z={}, w={}", z, w)?;
+            for y in self.height.clone() {
+                for x in self.width.clone() {
+                    let space = self.spaces.get(&(x, y, z, w));
+                    write!(f, "{}", space.copied().unwrap_or(Space::Inactive))?;
+                }
+                writeln!(f)?;
+            }
+        }
+        Ok(())
     }
 }
 
@@ -103,7 +166,8 @@ fn expand(range: &Range<isize>) -> Range<isize> {
     (range.start - 1)..(range.end + 1)
 }
 
-fn iterate(orig: &Grid) -> Grid {
+/// One cycle in three dimensions. The box grows by one in every direction.
+pub fn iterate(orig: &Grid) -> Grid {
     let mut next = Grid {
         width: expand(&orig.width),
         height: expand(&orig.height),
@@ -136,7 +200,8 @@ fn neighbours(index: (isize, isize, isize)) -> impl Iterator<Item = (isize, isiz
     iproduct!(xs, ys, zs).filter(move |&(x1, y1, z1)| { (x1, y1, z1) } != (x, y, z))
 }
 
-fn iterate4(orig: &Grid4) -> Grid4 {
+/// One cycle in four dimensions. The box grows by one in every direction.
+pub fn iterate4(orig: &Grid4) -> Grid4 {
     let mut next = Grid4 {
         width: expand(&orig.width),
         height: expand(&orig.height),
@@ -178,26 +243,24 @@ fn neighbours4(
     iproduct!(xs, ys, zs, ws).filter(move |&v| v != index)
 }
 
-fn new_grid<'a>(lines: impl Iterator<Item = &'a str> + Clone) -> Grid {
-    let width = lines.clone().next().unwrap().chars().count() as isize;
-    let height = lines.clone().count() as isize;
+/// The grid with the starting slice at `z = 0`.
+pub fn new_grid(input: &str) -> Result<Grid, ParseError> {
+    let slice = grid::Grid::parse(input, ". or #", |c| match c {
+        '#' => Some(Space::Active),
+        '.' => Some(Space::Inactive),
+        _ => None,
+    })?;
     let mut grid = Grid {
-        width: 0..width,
-        height: 0..height,
+        width: 0..slice.width() as isize,
+        height: 0..slice.height() as isize,
         depth: 0..1,
         spaces: HashMap::new(),
     };
-    for (y, line) in lines.enumerate() {
-        for (x, c) in line.chars().enumerate() {
-            let space = match c {
-                '#' => Space::Active,
-                '.' => Space::Inactive,
-                _ => unimplemented!(),
-            };
-            grid.spaces.insert((x as isize, y as isize, 0), space);
-        }
+    for (x, y) in slice.positions() {
+        grid.spaces
+            .insert((x as isize, y as isize, 0), slice[(x, y)]);
     }
-    grid
+    Ok(grid)
 }
 
 #[cfg(test)]
@@ -210,15 +273,10 @@ mod test {
 .#.
 ..#
 ###";
-        let lines = input.split("\n");
-        let original_grid = new_grid(lines);
+        let original_grid = new_grid(input).unwrap();
         let mut grid = original_grid.clone();
-        // println!("Before any cycles");
-        // grid.dump();
-        for _i in 0..6 {
+        for _ in 0..6 {
             grid = iterate(&grid);
-            // println!("After {} cycles:", _i + 1);
-            // grid.dump();
         }
         assert_eq!(
             grid.spaces
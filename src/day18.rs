@@ -0,0 +1,321 @@
+//! Day 18: Operation Order. Evaluate arithmetic where the precedence rules are different: first
+//! with `+` and `*` equal, then with `+` first.
+//!
+//! A line is split into [`Token`]s by [`tokenize`], and then read into an [`Expression`] by one of
+//! the readers, [`read_expression`] or [`read_expression2`], which know the precedence rules.
+
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use crate::input::parse_lines;
+use crate::{Answer, ParseError, Solution};
+
+/// The puzzle input is the homework, one expression per line, which parses to tokens.
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed = Vec<Vec<Token>>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input, tokenize)
+    }
+
+    fn part1(homework: &Self::Parsed) -> Answer {
+        let sum: usize = homework
+            .iter()
+            .map(|tokens| evaluate_tokens(tokens, read_expression))
+            .sum();
+        sum.into()
+    }
+
+    fn part2(homework: &Self::Parsed) -> Answer {
+        let sum: usize = homework
+            .iter()
+            .map(|tokens| evaluate_tokens(tokens, read_expression2))
+            .sum();
+        sum.into()
+    }
+}
+
+pub type TokenStream<'a> = Peekable<std::slice::Iter<'a, Token>>;
+/// Reads an expression from the tokens, up to the end of its bracketed group.
+pub type ExpressionReader = fn(&mut TokenStream) -> Expression;
+
+/// Reads an expression with the given precedence rules.
+pub fn parse_expression(
+    input: &str,
+    read_expression: ExpressionReader,
+) -> Result<Expression, ParseError> {
+    let tokens = tokenize(input)?;
+    Ok(read_expression(&mut tokens.iter().peekable()))
+}
+
+#[cfg(test)]
+fn evaluate(input: &str, read_expression: ExpressionReader) -> usize {
+    evaluate_tokens(&tokenize(input).unwrap(), read_expression)
+}
+
+/// Evaluates a tokenized line with the given precedence rules.
+pub fn evaluate_tokens(the_tokens: &[Token], read_expression: ExpressionReader) -> usize {
+    let mut tokens = the_tokens.iter().peekable();
+    let exp = read_expression(&mut tokens);
+    exp.evaluate()
+}
+
+/// A piece of a line. Spaces aren't tokens.
+#[derive(Debug, PartialEq)]
+pub enum Token {
+    OpenBracket,
+    CloseBracket,
+    Add,
+    Multiply,
+    Literal(usize),
+}
+
+/// Split a line into tokens. The readers assume operands and operators alternate and brackets
+/// balance, so check that here and they don't have to.
+pub fn tokenize(input: &str) -> Result<Vec<Token>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut depth = 0usize;
+    let mut expecting_operand = true;
+    let mut chars = input.char_indices().peekable();
+    while let Some((index, c)) = chars.next() {
+        let found = &input[index..index + c.len_utf8()];
+        let token = match c {
+            ' ' => continue,
+            c if c.is_ascii_digit() => match parse_number(input, &mut chars, index) {
+                Some(n) => Token::Literal(n),
+                None => return Err(ParseError::at(input, found, "a smaller number")),
+            },
+            '(' => Token::OpenBracket,
+            ')' => Token::CloseBracket,
+            '+' => Token::Add,
+            '*' => Token::Multiply,
+            _ => {
+                let expected = "a number, an operator or a bracket";
+                return Err(ParseError::at(input, found, expected));
+            }
+        };
+        match (&token, expecting_operand) {
+            (Token::OpenBracket, true) => depth += 1,
+            (Token::Literal(_), true) => expecting_operand = false,
+            (Token::CloseBracket, false) if depth > 0 => depth -= 1,
+            (Token::Add | Token::Multiply, false) => expecting_operand = true,
+            (_, true) => return Err(ParseError::at(input, found, "a number or (")),
+            (_, false) if depth > 0 => return Err(ParseError::at(input, found, "+, * or )")),
+            (_, false) => return Err(ParseError::at(input, found, "+ or *")),
+        }
+        tokens.push(token);
+    }
+    match (expecting_operand, depth) {
+        (true, _) => Err(ParseError::end(input, "a number or (")),
+        (false, 0) => Ok(tokens),
+        (false, _) => Err(ParseError::end(input, ")")),
+    }
+}
+
+fn parse_number(
+    input: &str,
+    chars: &mut Peekable<CharIndices>,
+    start_index: usize,
+) -> Option<usize> {
+    loop {
+        if let Some((_, c)) = chars.peek() {
+            if c.is_ascii_digit() {
+                chars.next();
+                continue;
+            }
+        }
+        break;
+    }
+    let end_index = chars.peek().map(|(i, _)| *i).unwrap_or(input.len());
+    let digits = &input[start_index..end_index];
+    digits.parse::<usize>().ok()
+}
+
+/// An expression tree, where the brackets and precedence are already taken into account.
+#[derive(Debug)]
+pub enum Expression {
+    Constant(usize),
+    Sum(Box<Expression>, Box<Expression>),
+    Product(Box<Expression>, Box<Expression>),
+}
+
+impl Expression {
+    pub fn evaluate(&self) -> usize {
+        match self {
+            Expression::Constant(u) => *u,
+            Expression::Sum(e1, e2) => e1.evaluate() + e2.evaluate(),
+            Expression::Product(e1, e2) => e1.evaluate() * e2.evaluate(),
+        }
+    }
+}
+
+/// Part 1's rules: `+` and `*` are equal, so everything goes left to right.
+pub fn read_expression(tokens: &mut TokenStream) -> Expression {
+    let first = read_term(tokens, read_expression);
+    let mut exp = Box::new(first);
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Add => {
+                exp = Box::new(Expression::Sum(
+                    exp,
+                    Box::new(read_term(tokens, read_expression)),
+                ))
+            }
+            Token::Multiply => {
+                exp = Box::new(Expression::Product(
+                    exp,
+                    Box::new(read_term(tokens, read_expression)),
+                ))
+            }
+            Token::CloseBracket => break,
+            _ => panic!(),
+        }
+    }
+    *exp
+}
+
+/// Part 2's rules: `+` comes before `*`.
+pub fn read_expression2(tokens: &mut TokenStream) -> Expression {
+    let first = read_summands(tokens, read_expression2);
+    let mut exp = Box::new(first);
+
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::Add => {
+                exp = Box::new(Expression::Sum(
+                    exp,
+                    Box::new(read_summands(tokens, read_expression2)),
+                ))
+            }
+            Token::Multiply => {
+                exp = Box::new(Expression::Product(
+                    exp,
+                    Box::new(read_summands(tokens, read_expression2)),
+                ))
+            }
+            Token::CloseBracket => break,
+            _ => panic!(),
+        }
+    }
+    *exp
+}
+
+fn read_term(tokens: &mut TokenStream, read_expression: ExpressionReader) -> Expression {
+    match tokens.next().unwrap() {
+        Token::OpenBracket => read_expression(tokens),
+        Token::CloseBracket => unreachable!(),
+        Token::Add => unreachable!(),
+        Token::Multiply => unreachable!(),
+        Token::Literal(u) => Expression::Constant(*u),
+    }
+}
+
+fn read_summands(tokens: &mut TokenStream, read_expression: ExpressionReader) -> Expression {
+    let read_summand = |tokens: &mut TokenStream| {
+        Box::new(match tokens.next().unwrap() {
+            Token::OpenBracket => read_expression(tokens),
+            Token::CloseBracket => unreachable!(),
+            Token::Add => unreachable!(),
+            Token::Multiply => unreachable!(),
+            Token::Literal(u) => Expression::Constant(*u),
+        })
+    };
+    let mut sum = read_summand(tokens);
+    while let Some(Token::Add) = tokens.peek() {
+        tokens.next();
+        let next_summand = read_summand(tokens);
+        sum = Box::new(Expression::Sum(sum, next_summand));
+    }
+    *sum
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generate::Prng;
+    use rand::{Rng, SeedableRng};
+
+    /// An expression in which every bracketed group uses only one operator.
+    fn unmixed_expression(rng: &mut Prng, depth: usize) -> String {
+        let operator = if rng.gen() { " + " } else { " * " };
+        let operands: Vec<_> = (0..rng.gen_range(1..=4))
+            .map(|_| {
+                if depth > 0 && rng.gen_bool(0.3) {
+                    format!("({})", unmixed_expression(rng, depth - 1))
+                } else {
+                    rng.gen_range(1..=9).to_string()
+                }
+            })
+            .collect();
+        operands.join(operator)
+    }
+
+    #[test]
+    fn example_1() {
+        assert_eq!(evaluate("1 + 2 * 3 + 4 * 5 + 6", read_expression), 71);
+        assert_eq!(evaluate("10 * (20 + 30) * 40", read_expression), 20000);
+        assert_eq!(evaluate("1 + (2 * 3) + (4 * (5 + 6))", read_expression), 51);
+        assert_eq!(evaluate("2 * 3 + (4 * 5)", read_expression), 26);
+        assert_eq!(
+            evaluate("5 + (8 * 3 + 9 + 3 * 4 * 3)", read_expression),
+            437
+        );
+        assert_eq!(
+            evaluate("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))", read_expression),
+            12240
+        );
+        assert_eq!(
+            evaluate(
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                read_expression
+            ),
+            13632,
+        );
+    }
+
+    #[test]
+    fn example_2() {
+        assert_eq!(evaluate("1 + 2 * 3 + 4 * 5 + 6", read_expression2), 231);
+        assert_eq!(evaluate("10 * (20 + 30) * 40", read_expression2), 20000);
+        assert_eq!(
+            evaluate("1 + (2 * 3) + (4 * (5 + 6))", read_expression2),
+            51
+        );
+        assert_eq!(evaluate("2 * 3 + (4 * 5)", read_expression2), 46);
+        assert_eq!(
+            evaluate("5 + (8 * 3 + 9 + 3 * 4 * 3)", read_expression2),
+            1445
+        );
+        assert_eq!(
+            evaluate(
+                "5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))",
+                read_expression2
+            ),
+            669060
+        );
+        assert_eq!(
+            evaluate(
+                "((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2",
+                read_expression2
+            ),
+            23340,
+        );
+    }
+
+    #[test]
+    fn precedence_only_matters_for_mixed_operators() {
+        let mut rng = Prng::seed_from_u64(18);
+        for _ in 0..500 {
+            let input = unmixed_expression(&mut rng, 3);
+            assert_eq!(
+                evaluate(&input, read_expression),
+                evaluate(&input, read_expression2),
+                "{}",
+                input
+            );
+        }
+    }
+}
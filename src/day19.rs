@@ -0,0 +1,385 @@
+//! Day 19: Monster Messages. Check messages against a grammar of numbered rules.
+//!
+//! Part 1 turns the grammar into a regular expression. Part 2's rules loop, so there the grammar
+//! is instead turned into [`Pattern`]s, which collapse to finite languages wherever they can, and
+//! the loops are handled by hand.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use itertools::{join, Itertools};
+use petgraph::prelude::GraphMap;
+use petgraph::visit::depth_first_search;
+use petgraph::visit::DfsEvent::Finish;
+use petgraph::Directed;
+use regex::Regex;
+
+use crate::input::records;
+use crate::scan::Scanner;
+use crate::{Answer, ParseError, Solution};
+
+/// The puzzle input: the rules, then the messages to check.
+pub struct Messages {
+    pub rules: HashMap<usize, Rule>,
+    pub examples: Vec<String>,
+}
+
+/// The puzzle input is the rules and the messages, separated by a blank line.
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed = Messages;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut sections = records(input);
+        let rules = read_rules(input, &sections.next().unwrap_or_default())?;
+        let examples = sections
+            .next()
+            .unwrap_or_default()
+            .into_iter()
+            .map(String::from)
+            .collect();
+        Ok(Messages { rules, examples })
+    }
+
+    fn part1(messages: &Self::Parsed) -> Answer {
+        let dependencies = dependency_graph(&messages.rules);
+
+        // Whole language has ~2M words, so test for that with a regex
+        let re = build_re(0, &messages.rules, &dependencies);
+        let re = format!("^{}$", &re[&0]);
+        let re = Regex::new(&re).unwrap();
+        messages
+            .examples
+            .iter()
+            .filter(|s| re.is_match(s))
+            .count()
+            .into()
+    }
+
+    fn part2(messages: &Self::Parsed) -> Answer {
+        let rules = &messages.rules;
+        if !rules.contains_key(&42) || !rules.contains_key(&31) {
+            return Answer::Unsolved;
+        }
+        let dependencies = dependency_graph(rules);
+
+        let patterns_42 = build_languages(42, rules, &dependencies);
+        let patterns_31 = build_languages(31, rules, &dependencies);
+
+        // Rule 0 is 8 11, which the new rules make 42{n} 31{m} with n > m >= 1. Both languages
+        // consist of 8-letter words and are disjoint, so a greedy match is enough.
+        let l42 = patterns_42[&42].as_lang().unwrap();
+        let l31 = patterns_31[&31].as_lang().unwrap();
+        messages
+            .examples
+            .iter()
+            .filter(|s| valid_new_ruleset(s, l42, l31))
+            .count()
+            .into()
+    }
+}
+
+/// Whether `s` matches part 2's rule 0, which is some words of `l42` followed by fewer, but at
+/// least one, words of `l31`. Both languages must consist of 8-letter words.
+pub fn valid_new_ruleset(s: &str, l42: &HashSet<String>, l31: &HashSet<String>) -> bool {
+    let mut count42 = 0;
+    let mut count31 = 0;
+    let mut i = 0;
+    // fortunately, these are ascii strings so can just fiddle with each byte
+    while i + 8 <= s.len() {
+        if l42.contains(&s[i..(i + 8)]) {
+            count42 += 1;
+            i += 8;
+        } else {
+            break;
+        }
+    }
+    while i + 8 <= s.len() {
+        if l31.contains(&s[i..(i + 8)]) {
+            count31 += 1;
+            i += 8;
+        } else {
+            break;
+        }
+    }
+    i == s.len() && count42 > count31 && count31 >= 1
+}
+
+/// A rule of the grammar, referring to other rules by number.
+#[derive(Debug)]
+pub enum Rule {
+    Literal(char),
+    ChoiceOfSequences(Vec<Vec<usize>>),
+    Sequence(Vec<usize>),
+}
+
+impl Rule {
+    /// Either a quoted character, or sequences of rule numbers separated by `|`.
+    pub fn parse(s: &mut Scanner) -> Result<Self, ParseError> {
+        if s.literal("\"").is_ok() {
+            let c = s.char()?;
+            s.literal("\"")?;
+            return Ok(Rule::Literal(c));
+        }
+        let mut choices = s.separated(" | ", |s| {
+            let mut sequence = vec![s.number()?];
+            while s.rest().starts_with(' ') && !s.rest().starts_with(" |") {
+                s.literal(" ")?;
+                sequence.push(s.number()?);
+            }
+            Ok(sequence)
+        })?;
+        if choices.len() == 1 {
+            Ok(Rule::Sequence(choices.remove(0)))
+        } else {
+            Ok(Rule::ChoiceOfSequences(choices))
+        }
+    }
+
+    /// The numbers of the rules this one refers to.
+    pub fn references(&self) -> Vec<usize> {
+        match self {
+            Rule::Literal(_) => vec![],
+            Rule::ChoiceOfSequences(choices) => choices.concat(),
+            Rule::Sequence(seq) => seq.clone(),
+        }
+    }
+}
+
+/// Read the rules, which are `lines` of `input`. There must be a rule 0, and every rule referred
+/// to must exist.
+pub fn read_rules(input: &str, lines: &[&str]) -> Result<HashMap<usize, Rule>, ParseError> {
+    let mut rules = HashMap::new();
+    let mut rule_lines = HashMap::new();
+    for &line in lines {
+        let mut s = Scanner::new(line);
+        let before = s.clone();
+        let id = s.number().map_err(|e| e.within(input, line))?;
+        if rule_lines.insert(id, line).is_some() {
+            return Err(before.error("a new rule number").within(input, line));
+        }
+        s.literal(": ").map_err(|e| e.within(input, line))?;
+        let rule = Rule::parse(&mut s).map_err(|e| e.within(input, line))?;
+        s.end().map_err(|e| e.within(input, line))?;
+        rules.insert(id, rule);
+    }
+    for (id, rule) in &rules {
+        if let Some(missing) = rule
+            .references()
+            .into_iter()
+            .find(|i| !rules.contains_key(i))
+        {
+            let line = rule_lines[id];
+            let number = missing.to_string();
+            let body = &line[line.find(": ").map_or(0, |i| i + 2)..];
+            let found = body.split(' ').find(|&t| t == number).unwrap_or(body);
+            return Err(ParseError::at(input, found, "a rule which exists"));
+        }
+    }
+    if !rules.contains_key(&0) {
+        let end = lines.last().map_or(input, |line| &line[line.len()..]);
+        return Err(ParseError::at(input, end, "a rule 0"));
+    }
+    Ok(rules)
+}
+
+/// An edge from each rule to each of the rules it refers to.
+pub fn dependency_graph(rules: &HashMap<usize, Rule>) -> GraphMap<usize, (), Directed> {
+    let mut g = GraphMap::new();
+    for (index, rule) in rules {
+        match rule {
+            Rule::Literal(_) => {
+                g.add_node(*index);
+            }
+            Rule::Sequence(seq) => {
+                seq.iter().for_each(|dependant_index| {
+                    g.add_edge(*index, *dependant_index, ());
+                });
+            }
+            Rule::ChoiceOfSequences(choices) => {
+                choices
+                    .iter()
+                    .flat_map(|c| c.iter())
+                    .for_each(|dependant_index| {
+                        g.add_edge(*index, *dependant_index, ());
+                    });
+            }
+        }
+    }
+    g
+}
+
+/// The strings a rule matches, as a finite language where possible.
+#[derive(Debug, Clone)]
+pub enum Pattern {
+    /// One string from each pattern, one after the other.
+    Concatenation(Vec<Rc<Pattern>>),
+    /// A string from any of the patterns.
+    OneOf(Vec<Rc<Pattern>>),
+    /// Exactly these strings.
+    Language(HashSet<String>),
+}
+
+impl Pattern {
+    /// The strings the pattern matches, if it has been simplified to a language.
+    pub fn as_lang(&self) -> Option<&HashSet<String>> {
+        match self {
+            Pattern::Language(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    fn get_languages(patterns: &[Rc<Pattern>]) -> Option<Vec<&HashSet<String>>> {
+        let languages: Vec<_> = patterns
+            .iter()
+            .filter_map(|p| {
+                if let Pattern::Language(s) = &**p {
+                    Some(s)
+                } else {
+                    None
+                }
+            })
+            .collect();
+        if languages.len() == patterns.len() {
+            Some(languages)
+        } else {
+            None
+        }
+    }
+}
+
+/// The pattern for `start_index` and every rule it depends on, each simplified as far as
+/// [`try_simplify`] can. The rules reachable from `start_index` mustn't loop.
+pub fn build_languages(
+    start_index: usize,
+    rules: &HashMap<usize, Rule>,
+    dependencies: &GraphMap<usize, (), Directed>,
+) -> HashMap<usize, Rc<Pattern>> {
+    let mut patterns = HashMap::<usize, Rc<Pattern>>::new();
+    depth_first_search(dependencies, Some(start_index), |event| {
+        if let Finish(index, _) = event {
+            let pattern_for_seq = |seq: &Vec<usize>| {
+                let ps: Vec<_> = seq.iter().map(|i| patterns[i].clone()).collect();
+                if ps.len() == 1 {
+                    try_simplify((*ps[0]).clone())
+                } else {
+                    try_simplify(Pattern::Concatenation(ps))
+                }
+            };
+            let pattern = match &rules[&index] {
+                Rule::Literal(c) => {
+                    let mut set = HashSet::new();
+                    set.insert(c.to_string());
+                    Pattern::Language(set)
+                }
+                Rule::ChoiceOfSequences(seqs) => try_simplify(Pattern::OneOf(
+                    seqs.iter().map(pattern_for_seq).map(Rc::new).collect(),
+                )),
+                Rule::Sequence(seq) => pattern_for_seq(seq),
+            };
+            trace!(
+                "Rule {}: {:?} generates {:?}",
+                index,
+                &rules[&index],
+                pattern
+            );
+            patterns.insert(index, Rc::new(pattern));
+        }
+    });
+    patterns
+}
+
+/// Collapses a concatenation or choice of languages into a single language. Anything else is
+/// returned unchanged.
+pub fn try_simplify(pattern: Pattern) -> Pattern {
+    match &pattern {
+        Pattern::Concatenation(patterns) => {
+            if let Some(languages) = Pattern::get_languages(patterns) {
+                let mut language = HashSet::new();
+                language.insert(String::new());
+                let language = languages.iter().fold(language, |l, r| {
+                    l.iter()
+                        .cartesian_product(r.iter())
+                        .map(|(a, b)| format!("{}{}", a, b))
+                        .collect()
+                });
+                return Pattern::Language(language);
+            }
+        }
+        Pattern::OneOf(patterns) => {
+            if let Some(languages) = Pattern::get_languages(patterns) {
+                let mut language = HashSet::new();
+                languages
+                    .iter()
+                    .for_each(|l| language.extend(l.iter().cloned()));
+                return Pattern::Language(language);
+            }
+        }
+        Pattern::Language(_) => {}
+    }
+    pattern
+}
+
+/// A regular expression for `start_index` and every rule it depends on, without anchors. The rules
+/// reachable from `start_index` mustn't loop.
+pub fn build_re(
+    start_index: usize,
+    rules: &HashMap<usize, Rule>,
+    dependencies: &GraphMap<usize, (), Directed>,
+) -> HashMap<usize, String> {
+    let mut patterns = HashMap::new();
+    depth_first_search(dependencies, Some(start_index), |event| {
+        if let Finish(index, _) = event {
+            let pattern_for_seq = |seq: &Vec<usize>| join(seq.iter().map(|i| &patterns[i]), "");
+            let pattern = match &rules[&index] {
+                Rule::Literal(c) => c.to_string(),
+                Rule::ChoiceOfSequences(choices) => {
+                    let choices = choices.iter().map(pattern_for_seq);
+                    format!("({})", join(choices, "|"))
+                }
+                Rule::Sequence(seq) => pattern_for_seq(seq),
+            };
+            trace!(
+                "Rule {}: {:?} generates {:?}",
+                index,
+                &rules[&index],
+                pattern
+            );
+            patterns.insert(index, pattern);
+        }
+    });
+    patterns
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_1() {
+        let input = "\
+0: 4 1 5
+1: 2 3 | 3 2
+2: 4 4 | 5 5
+3: 4 5 | 5 4
+4: \"a\"
+5: \"b\"
+
+ababbb
+bababa
+abbbab
+aaabbb
+aaaabbb";
+        let rules = read_rules(input, &records(input).next().unwrap()).unwrap();
+        let dependencies = dependency_graph(&rules);
+        let patterns = build_languages(0, &rules, &dependencies);
+        if let Pattern::Language(l) = &*patterns[&0] {
+            assert!(l.contains("ababbb"));
+            assert!(!l.contains("bababa"));
+            assert!(l.contains("abbbab"));
+            assert!(!l.contains("aaabbb"));
+            assert!(!l.contains("aaaabbb"));
+        }
+    }
+}
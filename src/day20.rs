@@ -0,0 +1,780 @@
+//! Day 20: Jurassic Jigsaw. Fit the tiles together into a picture, then look for sea
+//! monsters in it. The tiles themselves are in [`crate::lib20`]; this is the searching.
+
+use std::cmp::Reverse;
+use std::fmt;
+
+use strum::IntoEnumIterator;
+
+use crate::grid::Grid;
+use crate::lib20::*;
+use crate::pattern::Pattern;
+use crate::{Answer, ParseError, Solution};
+
+/// The puzzle input is the tiles. Its tiles make a square, but any rectangle will do.
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Parsed = Vec<Tile>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let tiles = read_tiles(input)?;
+        if tiles.is_empty() {
+            return Err(ParseError::end(input, "a tile"));
+        }
+        Ok(tiles)
+    }
+
+    fn part1(tiles: &Self::Parsed) -> Answer {
+        let tiles_by_edges = build_edge_lookup(tiles);
+        let compositions = search_for_compositions(tiles, &tiles_by_edges);
+        if log_enabled!(log::Level::Debug) {
+            for c in &compositions {
+                debug!("The tiles fit together {} by {}", c.width(), c.height());
+            }
+        }
+        match compositions.first() {
+            Some(c) => c.corners().into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn part2(tiles: &Self::Parsed) -> Answer {
+        let tiles_by_edges = build_edge_lookup(tiles);
+        let c = match search_for_composition(tiles, &tiles_by_edges) {
+            Some(c) => c,
+            None => return Answer::Unsolved,
+        };
+        match find_sea_monsters(&c.assemble()) {
+            Some(sighting) => {
+                debug!(
+                    "{} sea monsters with the image arranged {:?}, at {:?}",
+                    sighting.positions.len(),
+                    sighting.arrangement,
+                    sighting.positions
+                );
+                sighting.roughness.into()
+            }
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+/// The sea monster, as the puzzle draws it. Only the `#`s matter; the spaces may be anything.
+pub const SEA_MONSTER: [&str; 3] = [
+    "                  # ",
+    "#    ##    ##    ###",
+    " #  #  #  #  #  #   ",
+];
+
+lazy_static! {
+    static ref SEA_MONSTER_PATTERN: Pattern = Pattern::parse(&SEA_MONSTER.join("\n")).unwrap();
+}
+
+/// Sea monsters found in an image.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sighting {
+    /// How the monsters are turned, compared to how [`SEA_MONSTER`] draws them.
+    pub arrangement: RotoReflection,
+    /// The top left corner of each monster, turned that way, in the image as given. Monsters may
+    /// overlap.
+    pub positions: Vec<(usize, usize)>,
+    /// The number of `#`s which aren't part of any monster.
+    pub roughness: usize,
+}
+
+/// Looks for sea monsters turned each of the eight ways in an image of any shape, and reports the
+/// way with the most of them. Gives `None` if there are none at all.
+pub fn find_sea_monsters(image: &Grid<bool>) -> Option<Sighting> {
+    let search = SEA_MONSTER_PATTERN.search(image);
+    let (arrangement, positions) = RotoReflection::iter()
+        .map(|arrangement| {
+            let positions: Vec<_> = search
+                .matches
+                .iter()
+                .filter(|m| m.arrangement == arrangement)
+                .map(|m| (m.x, m.y))
+                .collect();
+            (arrangement, positions)
+        })
+        .filter(|(_, positions)| !positions.is_empty())
+        .max_by_key(|(_, positions)| positions.len())?;
+
+    // Only monsters turned this way count; any others are just waves.
+    let monster = SEA_MONSTER_PATTERN.arranged(arrangement);
+    let mut covered = Grid::filled(image.width(), image.height(), false);
+    for &(x, y) in &positions {
+        monster.cover(&mut covered, x, y);
+    }
+    let waves = image.iter().filter(|&&c| c).count();
+    Some(Sighting {
+        arrangement,
+        positions,
+        roughness: waves - covered.iter().filter(|&&c| c).count(),
+    })
+}
+
+#[derive(Debug)]
+struct Possibilities<'a> {
+    x: usize,
+    y: usize,
+    candidate: ArrangedTile<'a>,
+    other_options: Vec<ArrangedTile<'a>>,
+}
+
+impl fmt::Display for Possibilities<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "At ({},{}) considering {} with {} other alternatives",
+            self.x,
+            self.y,
+            self.candidate,
+            self.other_options.len()
+        )
+    }
+}
+
+/// An arrangement of all the tiles in which every edge matches, in the squarest shape that has
+/// one.
+pub fn search_for_composition<'a>(
+    tiles: &'a [Tile],
+    edge_lookup: &'a EdgeLookup,
+) -> Option<Composition<'a>> {
+    let analysis = Analysis::new(tiles, edge_lookup);
+    if analysis.unique {
+        return compose_greedily(&analysis, edge_lookup).into_iter().next();
+    }
+    shapes(tiles.len())
+        .into_iter()
+        .find_map(|(width, height)| search_for_shape(tiles, edge_lookup, width, height))
+}
+
+/// An arrangement of all the tiles for every shape that has one, squarest first. A shape and its
+/// transpose either both have one or neither does.
+pub fn search_for_compositions<'a>(
+    tiles: &'a [Tile],
+    edge_lookup: &'a EdgeLookup,
+) -> Vec<Composition<'a>> {
+    let analysis = Analysis::new(tiles, edge_lookup);
+    if analysis.unique {
+        return compose_greedily(&analysis, edge_lookup);
+    }
+    shapes(tiles.len())
+        .into_iter()
+        .filter_map(|(width, height)| search_for_shape(tiles, edge_lookup, width, height))
+        .collect()
+}
+
+/// The arrangements of all the tiles when their edges only match one way, squarest first, without
+/// any searching: each tile is the only one that fits beside the last. The picture can start
+/// from either of a corner tile's arrangements with nothing above it or to its left, one the
+/// transpose of the other.
+pub fn compose_greedily<'a>(
+    analysis: &Analysis<'a>,
+    edge_lookup: &'a EdgeLookup,
+) -> Vec<Composition<'a>> {
+    debug_assert!(analysis.unique);
+    let corner = match analysis.corners().next() {
+        Some(corner) => corner,
+        None => return Vec::new(),
+    };
+    let unmatched = |t: &ArrangedTile, e| {
+        let edge = OrientedEdge {
+            e,
+            o: Orientation::CW,
+        };
+        !edge_lookup.contains_key(t.edge_sum(edge))
+    };
+    let mut compositions: Vec<Composition> = corner
+        .arrangements()
+        .filter(|t| unmatched(t, Edge::Top) && unmatched(t, Edge::Left))
+        .filter_map(|t| compose_from(t, analysis.placements.len(), edge_lookup))
+        .collect();
+    compositions.sort_by_key(|c| (c.width().abs_diff(c.height()), Reverse(c.width())));
+    compositions.dedup_by_key(|c| (c.width(), c.height()));
+    compositions
+}
+
+/// Lays `count` tiles out from `start` in the top left, a row at a time. The first row is as long
+/// as its tiles keep matching.
+fn compose_from<'a>(
+    start: ArrangedTile<'a>,
+    count: usize,
+    edge_lookup: &'a EdgeLookup,
+) -> Option<Composition<'a>> {
+    let beside = |t: ArrangedTile<'a>, e| {
+        let edge = OrientedEdge {
+            e,
+            o: Orientation::CW,
+        };
+        let (src, next) = edge_lookup
+            .get(t.edge_sum(edge))?
+            .iter()
+            .find(|(_, next)| next.id != t.tile().id)?;
+        Some(ArrangedTile::such_that(next, *src, edge.opposite()))
+    };
+
+    let mut row = vec![start];
+    while let Some(t) = beside(*row.last().unwrap(), Edge::Right) {
+        if row.len() == count {
+            return None;
+        }
+        row.push(t);
+    }
+    let width = row.len();
+    if !count.is_multiple_of(width) {
+        return None;
+    }
+    let mut c = Composition::new(width, count / width);
+    for y in 0..c.height() {
+        for x in 0..width {
+            let t = match (x, y) {
+                (0, 0) => start,
+                (0, _) => beside(row[0], Edge::Bottom)?,
+                _ => beside(row[x - 1], Edge::Right)?,
+            };
+            if c.contains(t.tile().id) || !c.try_insert(t, x, y) {
+                return None;
+            }
+            row[x] = t;
+        }
+    }
+    debug!("Composed {} by {} without searching", c.width(), c.height());
+    Some(c)
+}
+
+/// A `width` by `height` arrangement of all the tiles in which every edge matches, found by
+/// depth-first search. There must be `width * height` tiles.
+pub fn search_for_shape<'a>(
+    tiles: &'a [Tile],
+    edge_lookup: &'a EdgeLookup,
+    width: usize,
+    height: usize,
+) -> Option<Composition<'a>> {
+    debug!("search for a {} by {} composition", width, height);
+    assert_eq!(
+        tiles.len(),
+        width * height,
+        "tiles for a {} by {}",
+        width,
+        height
+    );
+    // We have N = wh tiles and wish to see if they can be arranged into a w x h rectangle.
+    // The number of possibilities is vast: N! ways to organise the tiles into a rectangle,
+    // and then 8 roto reflections for each tile, for a total of N! 8^N possibilities.
+    // This is going to explode (the example data has N = 144) and so a brute force
+    // search is out of the question. (The example data has N = 144.)
+
+    // So we need an efficient way to prune this search space.
+    // We use a depth-first search to try and find a valid member of the state space.
+    // The state of our search is tracked in a "composition" c.
+    let mut c = Composition::new(width, height);
+
+    // (Note: every solution should appear 8 times accounting for rotations and reflections,
+    // because of the symmetry of the problem.)
+
+    // We start by guessing which tile and which orientation could be in the top-left corner.
+    // No cleverness here---try them all (8N possibilities).
+    let mut dfs_stack = Vec::new();
+    let mut options: Vec<ArrangedTile> = tiles.iter().flat_map(|t| t.arrangements()).collect();
+    dfs_stack.push(Possibilities {
+        x: 0,
+        y: 0,
+        // Think we can do better here. If there's a solution where the top left has orientation
+        // A, then VFlipCw90 that solution to get another solution with the same tile in the top
+        // left but a differnet orientation. So that's a factor of 2 to find somewhere.
+        candidate: options.pop().unwrap(),
+        other_options: options,
+    });
+
+    while !dfs_stack.is_empty() {
+        if log_enabled!(log::Level::Trace) {
+            trace!("Stack:");
+            let start = dfs_stack.len().saturating_sub(4);
+            dfs_stack
+                .iter()
+                .enumerate()
+                .skip(start)
+                .for_each(|(i, x)| trace!("  {:3}] {}", i, x));
+        }
+
+        // At first I just looked at `head` in place, but the borrow checker wasn't happy.
+        // So move its ownership to this function while we investigate it.
+        // Pick a tile arrangement out of our options for this square and see if it fits.
+        // Try inserting the tile. Does it cause any problems?
+        let outcome = try_insertion(dfs_stack.iter_mut().last().unwrap(), &mut c, edge_lookup);
+        match outcome {
+            InsertionOutcome::SuccessComplete => debug!("Yes! We're done!"),
+            InsertionOutcome::SuccessDescend(_) => trace!("Yes. Descend to another search level"),
+            InsertionOutcome::InsertionWouldClash => {
+                trace!("No: insertion would clash with tiles we've already tried")
+            }
+            InsertionOutcome::SuccessButNoOptions => {
+                trace!("No: insertion would work but we couldn't place a next tile.")
+            }
+        };
+
+        match outcome {
+            InsertionOutcome::SuccessButNoOptions | InsertionOutcome::InsertionWouldClash => {
+                trace!("stack contains {} things", dfs_stack.len());
+                while let Some(mut head) = dfs_stack.pop() {
+                    trace!("stack now contains {} things", dfs_stack.len());
+                    trace!("head we're considering is {}", head);
+                    c.clear(head.x, head.y);
+                    if let Some(t) = head.other_options.pop() {
+                        head.candidate = t;
+                        dfs_stack.push(head);
+                        break;
+                    }
+                }
+                match dfs_stack.last() {
+                    None => trace!("stack now empty"),
+                    Some(x) => trace!("stack now has {} things, head {}", dfs_stack.len(), x),
+                }
+            }
+            InsertionOutcome::SuccessDescend(p) => {
+                dfs_stack.push(p);
+            }
+            InsertionOutcome::SuccessComplete => {
+                return Some(c);
+            }
+        }
+    }
+    None
+}
+
+enum InsertionOutcome<'a> {
+    SuccessComplete,
+    SuccessDescend(Possibilities<'a>),
+    InsertionWouldClash,
+    SuccessButNoOptions,
+}
+
+fn try_insertion<'a, 'b>(
+    head: &'b mut Possibilities<'a>,
+    c: &'b mut Composition<'a>,
+    edge_lookup: &EdgeLookup<'a>,
+) -> InsertionOutcome<'a> {
+    trace!(
+        "Can we place {:?} at ({},{})?",
+        head.candidate,
+        head.x,
+        head.y
+    );
+
+    if c.try_insert(head.candidate, head.x, head.y) {
+        // If not, what square should we consider next?
+        let cell = next_cell(head.x, head.y, c.width(), c.height());
+        // Maybe we've considered all square and completed a composition of tiles.
+        if cell.is_none() {
+            return InsertionOutcome::SuccessComplete;
+        }
+
+        // But, more likely, we have to consider extra cells and descend down the dfs tree.
+        let (next_x, next_y, next_glue_edge) = cell.unwrap();
+        let (src_x, src_y) = step(next_glue_edge.e, next_x, next_y);
+        let edge_sum = c
+            .get_edge_sum(src_x, src_y, next_glue_edge.opposite())
+            .unwrap();
+        trace!(
+            "Yes. To continue, we need to glue ({},{}) edge {} to something with value {}. \
+            Options:",
+            src_x,
+            src_y,
+            next_glue_edge.opposite(),
+            edge_sum
+        );
+
+        const EMPTY: [(OrientedEdge, &Tile); 0] = [];
+        let options = match edge_lookup.get(edge_sum) {
+            Some(v) => v.as_slice(),
+            None => &EMPTY,
+        };
+        let mut options: Vec<_> = options
+            .iter()
+            .filter(|(_, t)| !c.contains(t.id))
+            .inspect(|(e, t)| trace!("    #{} {}", t.id, e))
+            .map(|(e, t)| ArrangedTile::such_that(t, *e, next_glue_edge))
+            .collect();
+
+        if !options.is_empty() {
+            let candidate = options.pop().unwrap();
+            let p = Possibilities {
+                x: next_x,
+                y: next_y,
+                candidate,
+                other_options: options,
+            };
+            InsertionOutcome::SuccessDescend(p)
+        } else {
+            InsertionOutcome::SuccessButNoOptions
+        }
+    } else {
+        InsertionOutcome::InsertionWouldClash
+    }
+}
+
+fn next_cell(
+    x: usize,
+    y: usize,
+    width: usize,
+    height: usize,
+) -> Option<(usize, usize, OrientedEdge)> {
+    // The next cell to consider is at (x, y).
+    // When choosing a tile to place here, start by looking for a match along the given edge.
+    if x == width - 1 {
+        if y == height - 1 {
+            None
+        } else {
+            Some((
+                0,
+                y + 1,
+                OrientedEdge {
+                    e: Edge::Top,
+                    o: Orientation::ACW,
+                },
+            ))
+        }
+    } else {
+        Some((
+            x + 1,
+            y,
+            OrientedEdge {
+                e: Edge::Left,
+                o: Orientation::ACW,
+            },
+        ))
+    }
+}
+
+fn step(e: Edge, x: usize, y: usize) -> (usize, usize) {
+    match e {
+        Edge::Top => (x, y - 1),
+        Edge::Right => (x + 1, y),
+        Edge::Bottom => (x, y - 1),
+        Edge::Left => (x - 1, y),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashSet;
+    use std::iter::FromIterator;
+
+    use strum::IntoEnumIterator;
+
+    use super::*;
+    use crate::examples;
+    use crate::grid::Grid;
+
+    fn example_one() -> String {
+        examples::text(20, "example.txt")
+    }
+
+    /// How the example's tiles fit together, as drawn in the puzzle.
+    fn example_one_assembled() -> String {
+        examples::text(20, "example.assembled")
+    }
+
+    #[test]
+    fn test_edge_lookup() {
+        let tiles1 = read_tiles(&example_one()).unwrap();
+        let tiles_by_edges1 = build_edge_lookup(&tiles1);
+
+        let tiles2 = read_tiles(&example_one()).unwrap();
+        let tiles_by_edges2 = build_edge_lookup(&tiles2);
+
+        assert_eq!(tiles_by_edges1.len(), tiles_by_edges2.len());
+        assert_eq!(
+            HashSet::<&EdgeSum>::from_iter(tiles_by_edges1.keys()),
+            HashSet::<&EdgeSum>::from_iter(tiles_by_edges2.keys())
+        );
+        for k in tiles_by_edges1.keys() {
+            let v1 = tiles_by_edges1.get(k).unwrap();
+            let v2 = tiles_by_edges1.get(k).unwrap();
+            assert_eq!(v1.len(), v2.len());
+
+            assert_eq!(
+                HashSet::<(OrientedEdge, usize)>::from_iter(
+                    v1.iter().cloned().map(|(e, t)| (e, t.id))
+                ),
+                HashSet::<(OrientedEdge, usize)>::from_iter(
+                    v2.iter().cloned().map(|(e, t)| (e, t.id))
+                )
+            );
+        }
+    }
+
+    #[test]
+    fn example_1() {
+        let tiles = read_tiles(&example_one()).unwrap();
+        let tiles_by_edges = build_edge_lookup(&tiles);
+        let c = search_for_composition(&tiles, &tiles_by_edges).unwrap();
+        assert_eq!(c.corners(), 20899048083289);
+        let image = c.assemble();
+        let expected_image =
+            Grid::parse(&example_one_assembled(), ". or #", |c| Some(c == '#')).unwrap();
+
+        assert_eq!((image.width(), image.height()), (24, 24));
+        assert_eq!((expected_image.width(), expected_image.height()), (24, 24));
+
+        let test = |arrangement: RotoReflection| {
+            let view = ArrangedSquareBitmap::new(arrangement, &image).unwrap();
+            expected_image
+                .positions()
+                .all(|(x, y)| expected_image[(x, y)] == *view.get(x, y).unwrap())
+        };
+        assert!(RotoReflection::iter().any(test));
+    }
+
+    #[test]
+    fn example_2() {
+        let tiles = read_tiles(&example_one()).unwrap();
+        let tiles_by_edges = build_edge_lookup(&tiles);
+        let c = search_for_composition(&tiles, &tiles_by_edges).unwrap();
+        let sighting = find_sea_monsters(&c.assemble()).unwrap();
+        assert_eq!(sighting.positions.len(), 2);
+        assert_eq!(sighting.roughness, 273);
+
+        // The same monsters are found however the image starts out. In the puzzle's picture of it,
+        // they lie on their sides.
+        let expected_image =
+            Grid::parse(&example_one_assembled(), ". or #", |c| Some(c == '#')).unwrap();
+        let mut from_expected = find_sea_monsters(&expected_image).unwrap();
+        from_expected.positions.sort_unstable();
+        assert_eq!(from_expected.arrangement, RotoReflection::VFlipCW90);
+        assert_eq!(from_expected.positions, vec![(2, 2), (16, 1)]);
+        assert_eq!(from_expected.roughness, 273);
+    }
+
+    #[test]
+    fn overlapping_sea_monsters() {
+        // Two monsters, two cells apart so that they share a cell, on a calm sea.
+        let mut picture = vec![vec![' '; 22]; 3];
+        for &x0 in &[0, 2] {
+            for (y, row) in SEA_MONSTER.iter().enumerate() {
+                for (x, c) in row.char_indices() {
+                    if c == '#' {
+                        picture[y][x0 + x] = '#';
+                    }
+                }
+            }
+        }
+        let mut rows: Vec<String> = picture.into_iter().map(String::from_iter).collect();
+        rows.resize(22, " ".repeat(22));
+        let image = Grid::parse(&rows.join("\n"), "# or space", |c| Some(c == '#')).unwrap();
+        let sighting = find_sea_monsters(&image).unwrap();
+        assert_eq!(sighting.arrangement, RotoReflection::None);
+        assert_eq!(sighting.positions, vec![(0, 0), (2, 0)]);
+        assert_eq!(sighting.roughness, 0);
+    }
+
+    #[test]
+    fn sea_monsters_turned_two_ways() {
+        // Two monsters the right way up, and one upside down beneath them.
+        let mut picture = vec![vec![' '; 42]; 7];
+        let upside_down: Vec<&str> = SEA_MONSTER.iter().rev().copied().collect();
+        for (rows, x0, y0) in [
+            (SEA_MONSTER.to_vec(), 0, 0),
+            (SEA_MONSTER.to_vec(), 22, 0),
+            (upside_down, 0, 4),
+        ] {
+            for (y, row) in rows.iter().enumerate() {
+                for (x, c) in row.char_indices() {
+                    if c == '#' {
+                        picture[y0 + y][x0 + x] = '#';
+                    }
+                }
+            }
+        }
+        let rows: Vec<String> = picture.into_iter().map(String::from_iter).collect();
+        let image = Grid::parse(&rows.join("\n"), "# or space", |c| Some(c == '#')).unwrap();
+        let sighting = find_sea_monsters(&image).unwrap();
+        assert_eq!(sighting.arrangement, RotoReflection::None);
+        assert_eq!(sighting.positions, vec![(0, 0), (22, 0)]);
+        assert_eq!(sighting.roughness, 15);
+    }
+
+    #[test]
+    fn large_tiles() {
+        use crate::days::Part;
+        use crate::generate::{jigsaw, Mode, Prng};
+        use rand::SeedableRng;
+
+        let mut rng = Prng::seed_from_u64(23);
+        for &tile in &[12, 17, 24, 70] {
+            let generated = jigsaw(&mut rng, 3, 3, tile, Mode::Known);
+            let tiles = read_tiles(&generated.input).unwrap();
+            assert!(tiles.iter().all(|t| t.size() == tile));
+            let tiles_by_edges = build_edge_lookup(&tiles);
+            let c = search_for_composition(&tiles, &tiles_by_edges).unwrap();
+            assert_eq!(
+                vec![(Part::One, c.corners().to_string())],
+                generated.expected,
+                "{} wide",
+                tile
+            );
+            let image = c.assemble();
+            assert_eq!(
+                (image.width(), image.height()),
+                (3 * (tile - 2), 3 * (tile - 2))
+            );
+        }
+    }
+
+    #[test]
+    fn rectangles() {
+        use crate::days::Part;
+        use crate::generate::{jigsaw, Mode, Prng};
+        use rand::SeedableRng;
+
+        let mut rng = Prng::seed_from_u64(24);
+        for &(width, height) in &[(1, 1), (1, 5), (2, 6), (4, 3), (7, 2)] {
+            let generated = jigsaw(&mut rng, width, height, 10, Mode::Known);
+            let tiles = read_tiles(&generated.input).unwrap();
+            let tiles_by_edges = build_edge_lookup(&tiles);
+            let compositions = search_for_compositions(&tiles, &tiles_by_edges);
+
+            let mut found: Vec<_> = compositions
+                .iter()
+                .map(|c| (c.width(), c.height()))
+                .collect();
+            found.sort_unstable();
+            let mut expected = vec![(width, height), (height, width)];
+            expected.sort_unstable();
+            expected.dedup();
+            assert_eq!(found, expected);
+            for c in &compositions {
+                assert_eq!(
+                    vec![(Part::One, c.corners().to_string())],
+                    generated.expected,
+                    "{} by {}",
+                    c.width(),
+                    c.height()
+                );
+                let image = c.assemble();
+                assert_eq!(
+                    (image.width(), image.height()),
+                    (8 * c.width(), 8 * c.height())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn rectangular_sea() {
+        use itertools::Itertools;
+
+        // Six of the example's nine tiles make a rectangle if they're two whole rows or columns
+        // of it. Only two of those rectangles are long enough along the monsters to hold one each.
+        let example = example_one();
+        let tiles: Vec<&str> = example.split("\n\n").collect();
+        let mut answers: Vec<String> = Vec::new();
+        for subset in tiles.iter().combinations(6) {
+            let input = subset.into_iter().join("\n\n");
+            let parsed = Day20::parse(&input).unwrap();
+            if Day20::part1(&parsed) != Answer::Unsolved {
+                answers.push(Day20::part2(&parsed).to_string());
+            }
+        }
+        answers.sort_unstable();
+
+        // The monsters lie on their sides in the puzzle's picture, so the rectangles holding them
+        // are its left and right two thirds.
+        let roughness = |columns: std::ops::Range<usize>| {
+            let waves: usize = example_one_assembled()
+                .lines()
+                .map(|row| row[columns.clone()].matches('#').count())
+                .sum();
+            (waves - SEA_MONSTER.join("").matches('#').count()).to_string()
+        };
+        let mut expected = vec![
+            roughness(0..16),
+            roughness(8..24),
+            "(unsolved)".to_owned(),
+            "(unsolved)".to_owned(),
+        ];
+        expected.sort_unstable();
+        assert_eq!(answers, expected);
+    }
+
+    #[test]
+    fn greedy_agrees_with_search() {
+        let tiles = read_tiles(&example_one()).unwrap();
+        let tiles_by_edges = build_edge_lookup(&tiles);
+        let analysis = Analysis::new(&tiles, &tiles_by_edges);
+        assert!(analysis.unique);
+        let greedy = compose_greedily(&analysis, &tiles_by_edges);
+        assert_eq!(greedy.len(), 1);
+        let searched = search_for_shape(&tiles, &tiles_by_edges, 3, 3).unwrap();
+        assert_eq!(greedy[0].corners(), searched.corners());
+        // The two may be turned differently, and their monsters with them.
+        let found = |c: &Composition| {
+            let sighting = find_sea_monsters(&c.assemble()).unwrap();
+            (sighting.positions.len(), sighting.roughness)
+        };
+        assert_eq!(found(&greedy[0]), found(&searched));
+    }
+
+    #[test]
+    fn ambiguous_tiles_are_searched() {
+        // Blank tiles fit together every way.
+        let blank = "..........\n".repeat(10);
+        let input = (1..=4)
+            .map(|id| format!("Tile {}:\n{}", id, blank))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let tiles = read_tiles(&input).unwrap();
+        let tiles_by_edges = build_edge_lookup(&tiles);
+        assert!(!Analysis::new(&tiles, &tiles_by_edges).unique);
+        let shapes: Vec<_> = search_for_compositions(&tiles, &tiles_by_edges)
+            .iter()
+            .map(|c| (c.width(), c.height(), c.corners()))
+            .collect();
+        assert_eq!(shapes, vec![(2, 2, 24), (4, 1, 4), (1, 4, 4)]);
+    }
+
+    #[test]
+    fn backtracking_from_a_clash() {
+        // The search used to clear the cell of a tile which had clashed, so was never placed.
+        let input = "\
+Tile 1:\n.##\n..#\n##.\n\nTile 2:\n.#.\n.##\n#..\n\n\
+Tile 3:\n#..\n##.\n...\n\nTile 4:\n.#.\n.#.\n##.\n";
+        let tiles = Day20::parse(input).unwrap();
+        assert_eq!(Day20::part1(&tiles), Answer::Number(24));
+
+        use crate::generate::Prng;
+        use rand::{Rng, SeedableRng};
+
+        let mut rng = Prng::seed_from_u64(20);
+        for _ in 0..300 {
+            let mut input = String::new();
+            for id in 1..=4 {
+                input.push_str(&format!("Tile {}:\n", id));
+                for _ in 0..3 {
+                    input.extend((0..3).map(|_| if rng.gen() { '#' } else { '.' }));
+                    input.push('\n');
+                }
+                input.push('\n');
+            }
+            let tiles = read_tiles(&input).unwrap();
+            let tiles_by_edges = build_edge_lookup(&tiles);
+            for c in search_for_compositions(&tiles, &tiles_by_edges) {
+                assert_eq!(c.ids.len(), 4);
+            }
+        }
+    }
+
+    #[test]
+    fn example_1_reversed() {
+        let tiles: Vec<_> = read_tiles(&example_one())
+            .unwrap()
+            .into_iter()
+            .rev()
+            .collect();
+        let tiles_by_edges = build_edge_lookup(&tiles);
+        let c = search_for_composition(&tiles, &tiles_by_edges).unwrap();
+        assert_eq!(c.corners(), 20899048083289);
+    }
+}
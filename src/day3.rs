@@ -0,0 +1,111 @@
+//! Day 3: Toboggan Trajectory. Count the trees on a straight path down the slope.
+
+use crate::grid::{Grid, Wrap};
+use crate::{Answer, ParseError, Solution};
+
+/// A square of the map.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum Tile {
+    Open,
+    Tree,
+}
+
+impl Tile {
+    fn parse(c: char) -> Option<Self> {
+        match c {
+            '.' => Some(Tile::Open),
+            '#' => Some(Tile::Tree),
+            _ => None,
+        }
+    }
+}
+
+/// The slope, which repeats itself forever to the right.
+pub type Map = Grid<Tile>;
+
+/// Reads the map from lines of `.` and `#`.
+pub fn parse_map(input: &str) -> Result<Map, ParseError> {
+    Grid::parse(input, ". or #", Tile::parse).map(|map| map.with_wrap(Wrap::Horizontal))
+}
+
+struct TobogganRide<'a> {
+    location: [usize; 2],
+    direction: [usize; 2],
+    map: &'a Map,
+}
+
+impl Iterator for TobogganRide<'_> {
+    type Item = Tile;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let [x, y] = self.location;
+        self.map.get(x as isize, y as isize).copied().inspect(|_c| {
+            self.location[0] += self.direction[0];
+            self.location[1] += self.direction[1];
+        })
+    }
+}
+
+/// The slopes to try in part 2, each as `[right, down]`.
+pub const DIRECTIONS: [[usize; 2]; 5] = [[1, 1], [3, 1], [5, 1], [7, 1], [1, 2]];
+
+/// The puzzle input is the map.
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed = Map;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_map(input)
+    }
+
+    fn part1(map: &Self::Parsed) -> Answer {
+        trees_hit(map, [3, 1]).into()
+    }
+
+    fn part2(map: &Self::Parsed) -> Answer {
+        DIRECTIONS
+            .iter()
+            .map(|dir| trees_hit(map, *dir))
+            .product::<usize>()
+            .into()
+    }
+}
+
+/// The number of trees hit by going `[right, down]` from the top left until falling off the
+/// bottom.
+pub fn trees_hit(map: &Map, direction: [usize; 2]) -> usize {
+    let path = TobogganRide {
+        location: [0, 0],
+        direction,
+        map,
+    };
+    path.filter(|&t| t == Tile::Tree).count()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    fn example() -> String {
+        examples::text(3, "example.txt")
+    }
+
+    #[test]
+    fn example_toboggan_ride() {
+        let map = parse_map(&example()).unwrap();
+        assert_eq!(trees_hit(&map, [3, 1]), 7);
+    }
+
+    #[test]
+    fn lots_of_toboggan_rides() {
+        let map = parse_map(&example()).unwrap();
+        let trees_hit: Vec<_> = DIRECTIONS
+            .iter()
+            .map(|&direction| trees_hit(&map, direction))
+            .collect();
+        assert_eq!(trees_hit, vec![2, 7, 3, 4, 2]);
+        assert_eq!(trees_hit.iter().product::<usize>(), 336);
+    }
+}
@@ -0,0 +1,363 @@
+//! Day 4: Passport Processing. Check batches of passport listings for missing and invalid fields.
+
+use regex::Regex;
+
+use crate::input::records;
+use crate::{Answer, ParseError, Solution};
+
+/// A height, in whichever unit it was written in.
+#[derive(Debug)]
+pub enum Height {
+    Centimeters(usize),
+    Inches(usize),
+}
+
+impl Height {
+    /// Reads a number followed by `cm` or `in`.
+    pub fn parse(input: &str) -> Option<Self> {
+        if let Ok((val, unit)) = scan_fmt!(input, "{d}{}", usize, String) {
+            match unit.as_str() {
+                "cm" => Some(Height::Centimeters(val)),
+                "in" => Some(Height::Inches(val)),
+                _ => None,
+            }
+        } else {
+            None
+        }
+    }
+
+    /// Whether the height is within the allowed range for its unit.
+    pub fn valid(&self) -> bool {
+        match self {
+            Height::Centimeters(v) => (150..=193).contains(v),
+            Height::Inches(v) => (59..=76).contains(v),
+        }
+    }
+}
+
+/// A hair colour.
+#[derive(Debug)]
+pub struct RGBColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+impl RGBColor {
+    /// Reads a colour like `#123abc`.
+    pub fn parse(input: &str) -> Option<Self> {
+        scan_fmt!(input, "#{2x}{2x}{2x}", [hex u8], [hex u8], [hex u8])
+            .ok()
+            .map(|(r, g, b)| Self { r, g, b })
+    }
+}
+
+/// An eye colour; there are only seven.
+#[derive(Debug)]
+pub enum EyeColor {
+    Amber,
+    Blue,
+    Brown,
+    Grey,
+    Green,
+    Hazel,
+    Other,
+}
+
+impl EyeColor {
+    /// Reads one of the three-letter colour codes.
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "amb" => Some(Self::Amber),
+            "blu" => Some(Self::Blue),
+            "brn" => Some(Self::Brown),
+            "gry" => Some(Self::Grey),
+            "grn" => Some(Self::Green),
+            "hzl" => Some(Self::Hazel),
+            "oth" => Some(Self::Other),
+            _ => None,
+        }
+    }
+}
+
+/// A passport whose required fields are all present and valid.
+#[derive(Debug)]
+pub struct Passport {
+    pub birth_year: usize,
+    pub issue_year: usize,
+    pub expiration_year: usize,
+    pub height: Height,
+    pub hair_color: RGBColor,
+    pub eye_color: EyeColor,
+    pub passport_id: usize,
+    /// The North Pole doesn't issue country ids, so this is optional.
+    pub country_id: Option<String>,
+}
+
+#[derive(Debug)]
+struct ProtoPassport {
+    birth_year: Option<usize>,
+    issue_year: Option<usize>,
+    expiration_year: Option<usize>,
+    height: Option<Height>,
+    hair_color: Option<RGBColor>,
+    eye_color: Option<EyeColor>,
+    passport_id: Option<usize>,
+    country_id: Option<String>,
+}
+
+lazy_static! {
+    static ref PASSPORT_ID_PATTERN: Regex = Regex::new(r"^\d{9}$").unwrap();
+}
+
+impl ProtoPassport {
+    fn new() -> Self {
+        Self {
+            birth_year: None,
+            issue_year: None,
+            expiration_year: None,
+            height: None,
+            hair_color: None,
+            eye_color: None,
+            passport_id: None,
+            country_id: None,
+        }
+    }
+
+    fn feed(&mut self, key: &str, value: &str) {
+        let parse = str::parse::<usize>;
+        match key {
+            "byr" => self.birth_year = parse(value).ok().filter(|y| (1920..=2002).contains(y)),
+            "iyr" => self.issue_year = parse(value).ok().filter(|y| (2010..=2020).contains(y)),
+            "eyr" => self.expiration_year = parse(value).ok().filter(|y| (2020..=2030).contains(y)),
+            "hgt" => self.height = Height::parse(value).filter(Height::valid),
+            "hcl" => self.hair_color = RGBColor::parse(value),
+            "ecl" => self.eye_color = EyeColor::parse(value),
+            "pid" => {
+                self.passport_id = {
+                    if PASSPORT_ID_PATTERN.is_match(value) {
+                        Some(parse(value).unwrap())
+                    } else {
+                        None
+                    }
+                }
+            }
+            "cid" => self.country_id = Some(value.to_owned()),
+            _ => (),
+        }
+    }
+
+    fn complete(self) -> Option<Passport> {
+        match self {
+            ProtoPassport {
+                birth_year: Some(birth_year),
+                issue_year: Some(issue_year),
+                expiration_year: Some(expiration_year),
+                height: Some(height),
+                hair_color: Some(hair_color),
+                eye_color: Some(eye_color),
+                passport_id: Some(passport_id),
+                country_id,
+            } => Some(Passport {
+                birth_year,
+                issue_year,
+                expiration_year,
+                height,
+                hair_color,
+                eye_color,
+                passport_id,
+                country_id,
+            }),
+            _ => {
+                debug!("INVALID: {:?}", self);
+                None
+            }
+        }
+    }
+}
+
+/// The whitespace-separated `key:value` fields of a listing.
+fn fields(input: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    input
+        .split_whitespace()
+        .map(|field| {
+            field
+                .split_once(':')
+                .ok_or_else(|| ParseError::at(input, field, "a key:value field"))
+        })
+        .collect()
+}
+
+impl Passport {
+    /// Reads whitespace-separated `key:value` fields, or gives `None` if a required field is
+    /// missing or invalid. Only text which isn't a field at all is an error.
+    pub fn parse(input: &str) -> Result<Option<Self>, ParseError> {
+        let mut passport = ProtoPassport::new();
+        for (key, value) in fields(input)? {
+            passport.feed(key, value);
+        }
+        Ok(passport.complete())
+    }
+}
+
+/// The keys every passport must have. `cid` is left out on purpose.
+pub const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+/// A passport listing: the keys it has, and the passport they make if they're all valid.
+pub struct Listing {
+    pub keys: Vec<String>,
+    pub passport: Option<Passport>,
+}
+
+impl Listing {
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        trace!("{:?}", input);
+        let keys = fields(input)?
+            .into_iter()
+            .map(|(key, _)| key.to_owned())
+            .collect();
+        let passport = Passport::parse(input)?;
+        Ok(Self { keys, passport })
+    }
+
+    /// Whether the listing has every required key, whatever the values are.
+    pub fn has_required_fields(&self) -> bool {
+        REQUIRED_FIELDS
+            .iter()
+            .all(|&field| self.keys.iter().any(|key| key == field))
+    }
+}
+
+/// The puzzle input is passport listings separated by blank lines.
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed = Vec<Listing>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        records(input)
+            .map(|record| {
+                // Each line of a record starts a line of the input, so only the line moves.
+                Listing::parse(&record.join("\n")).map_err(|e| e.within(input, record[0]))
+            })
+            .collect()
+    }
+
+    fn part1(listings: &Self::Parsed) -> Answer {
+        listings
+            .iter()
+            .filter(|listing| listing.has_required_fields())
+            .count()
+            .into()
+    }
+
+    fn part2(listings: &Self::Parsed) -> Answer {
+        let passports: Vec<_> = listings.iter().flat_map(|l| &l.passport).collect();
+        for passport in &passports {
+            debug!("{:?}", passport);
+        }
+        passports.len().into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn test_parser() {
+        let feed = |key, value| {
+            let mut x = ProtoPassport::new();
+            x.feed(key, value);
+            x
+        };
+        assert!(feed("byr", "1919").birth_year.is_none());
+        assert!(feed("byr", "1920").birth_year.is_some());
+        assert!(feed("byr", "2002").birth_year.is_some());
+        assert!(feed("byr", "2003").birth_year.is_none());
+
+        assert!(feed("iyr", "2009").issue_year.is_none());
+        assert!(feed("iyr", "2010").issue_year.is_some());
+        assert!(feed("iyr", "2020").issue_year.is_some());
+        assert!(feed("iyr", "2021").issue_year.is_none());
+
+        assert!(feed("eyr", "2019").expiration_year.is_none());
+        assert!(feed("eyr", "2020").expiration_year.is_some());
+        assert!(feed("eyr", "2030").expiration_year.is_some());
+        assert!(feed("eyr", "2031").expiration_year.is_none());
+
+        assert!(feed("hgt", "149cm").height.is_none());
+        assert!(feed("hgt", "150cm").height.is_some());
+        assert!(feed("hgt", "193cm").height.is_some());
+        assert!(feed("hgt", "194cm").height.is_none());
+        assert!(feed("hgt", "149").height.is_none());
+        assert!(feed("hgt", "150").height.is_none());
+        assert!(feed("hgt", "193").height.is_none());
+        assert!(feed("hgt", "194").height.is_none());
+        assert!(feed("hgt", "58in").height.is_none());
+        assert!(feed("hgt", "59in").height.is_some());
+        assert!(feed("hgt", "76in").height.is_some());
+        assert!(feed("hgt", "77in").height.is_none());
+        assert!(feed("hgt", "58").height.is_none());
+        assert!(feed("hgt", "59").height.is_none());
+        assert!(feed("hgt", "76").height.is_none());
+        assert!(feed("hgt", "77").height.is_none());
+
+        assert!(feed("hcl", "#123abc").height.is_none());
+        assert!(feed("hcl", "#123abz").height.is_none());
+        assert!(feed("hcl", "#123abc").height.is_none());
+        assert!(feed("hcl", "123abz").height.is_none());
+
+        assert!(feed("ecl", "amb").eye_color.is_some());
+        assert!(feed("ecl", "blu").eye_color.is_some());
+        assert!(feed("ecl", "brn").eye_color.is_some());
+        assert!(feed("ecl", "gry").eye_color.is_some());
+        assert!(feed("ecl", "grn").eye_color.is_some());
+        assert!(feed("ecl", "hzl").eye_color.is_some());
+        assert!(feed("ecl", "oth").eye_color.is_some());
+        assert!(feed("ecl", "zzz").eye_color.is_none());
+
+        assert!(feed("pid", "000000001").passport_id.is_some());
+        assert!(feed("pid", "0123456789").passport_id.is_none());
+    }
+
+    #[test]
+    fn examples() {
+        let valid: Vec<_> = Day4::parse(&examples::text(4, "example.txt"))
+            .unwrap()
+            .iter()
+            .map(|l| l.passport.is_some())
+            .collect();
+        assert_eq!(valid, vec![true, false, true, false]);
+    }
+
+    #[test]
+    fn invalid() {
+        let invalid: Vec<_> = Day4::parse(&examples::text(4, "invalid.txt"))
+            .unwrap()
+            .iter()
+            .map(|l| l.passport.is_some())
+            .collect();
+        assert_eq!(invalid, vec![false; 4]);
+    }
+
+    #[test]
+    fn valid() {
+        let valid: Vec<_> = Day4::parse(&examples::text(4, "valid.txt"))
+            .unwrap()
+            .iter()
+            .map(|l| l.passport.is_some())
+            .collect();
+        assert_eq!(valid, vec![true; 4]);
+    }
+
+    #[test]
+    fn not_a_field() {
+        let error = Day4::parse("byr:1937\n\niyr:2017 cid:147\nhgt 183cm\n")
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (4, 1));
+        assert_eq!(error.found, "hgt");
+    }
+}
@@ -0,0 +1,146 @@
+//! Day 5: Binary Boarding. Decode boarding passes, which are seat numbers written in binary
+//! with letters for digits.
+
+use std::ops::RangeInclusive;
+
+use crate::input::parse_lines;
+use crate::{Answer, ParseError, Solution};
+
+/// A seat on the plane: one of 128 rows, and one of 8 seats across each row.
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct Seat {
+    pub row: u8,
+    pub column: u8,
+}
+
+impl Seat {
+    /// The seat's unique id.
+    pub fn id(&self) -> u16 {
+        self.row as u16 * 8 + self.column as u16
+    }
+
+    /// The seat a boarding pass is for. The pass should already have been checked with
+    /// [`check_boarding_pass`]; this panics on anything else.
+    pub fn new(desc: &str) -> Self {
+        let mut rows: RangeInclusive<u8> = 0..=128;
+        let mut half_width = 64;
+        for char in desc[0..7].chars() {
+            match char {
+                'F' => rows = *rows.start()..=(*rows.start() + half_width),
+                'B' => rows = (*rows.end() - half_width)..=*rows.end(),
+                _ => unreachable!(),
+            }
+            half_width /= 2;
+        }
+
+        let mut cols: RangeInclusive<u8> = 0..=8;
+        let mut half_height = 4;
+        for char in desc[7..10].chars() {
+            match char {
+                'L' => cols = *cols.start()..=(*cols.start() + half_height),
+                'R' => cols = (*cols.end() - half_height)..=*cols.end(),
+                _ => unreachable!(),
+            }
+            half_height /= 2;
+        }
+        Self {
+            row: *rows.start(),
+            column: *cols.start(),
+        }
+    }
+}
+
+/// Seven of F or B, then three of L or R.
+pub fn check_boarding_pass(pass: &str) -> Result<&str, ParseError> {
+    for (i, c) in pass.char_indices() {
+        let (allowed, expected) = match i {
+            0..=6 => (['F', 'B'], "F or B"),
+            7..=9 => (['L', 'R'], "L or R"),
+            _ => return Err(ParseError::at(pass, &pass[i..], "the end of the line")),
+        };
+        if !allowed.contains(&c) {
+            let found = &pass[i..i + c.len_utf8()];
+            return Err(ParseError::at(pass, found, expected));
+        }
+    }
+    match pass.len() {
+        10 => Ok(pass),
+        0..=6 => Err(ParseError::end(pass, "F or B")),
+        _ => Err(ParseError::end(pass, "L or R")),
+    }
+}
+
+/// The puzzle input is one boarding pass per line, which parses to the sorted seat ids.
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed = Vec<u16>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let mut ids: Vec<_> = parse_lines(input, |line| {
+            check_boarding_pass(line).map(|pass| Seat::new(pass).id())
+        })?;
+        ids.sort_unstable();
+        Ok(ids)
+    }
+
+    fn part1(ids: &Self::Parsed) -> Answer {
+        match ids.last() {
+            Some(&id) => id.into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn part2(ids: &Self::Parsed) -> Answer {
+        match ids.windows(2).find(|pair| pair[0] + 2 == pair[1]) {
+            Some(pair) => (pair[0] + 1).into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn example(desc: &str, row: u8, column: u8, id: u16) {
+        let seat = Seat::new(desc);
+        assert_eq!(seat.row, row);
+        assert_eq!(seat.column, column);
+        assert_eq!(seat.id(), id);
+    }
+
+    #[test]
+    fn test() {
+        example("FBFBBFFRLR", 44, 5, 357);
+        example("BFFFBBFRRR", 70, 7, 567);
+        example("FFFBBBFRRR", 14, 7, 119);
+        example("BBFFBBFRLL", 102, 4, 820);
+    }
+
+    #[test]
+    fn invalid_passes() {
+        let error = |pass| {
+            let e = check_boarding_pass(pass).unwrap_err();
+            (e.column, e.expected, e.found)
+        };
+        // Letters and spaces from the expected message aren't allowed.
+        assert_eq!(error("FBoFBFBRRR"), (3, "F or B".into(), "o".into()));
+        assert_eq!(error("FB FBFBRRR"), (3, "F or B".into(), " ".into()));
+        assert_eq!(error("FBFBFBBLoR"), (9, "L or R".into(), "o".into()));
+        assert_eq!(error("FBFB"), (5, "F or B".into(), "".into()));
+        assert_eq!(
+            error("FBFBFBBRRRL"),
+            (11, "the end of the line".into(), "L".into())
+        );
+        assert!(Day5::parse("FBFBBFFRLR\nFBoFBFBRRR\n").is_err());
+    }
+
+    #[test]
+    fn no_empty_seat() {
+        let ids = Day5::parse("FBFBBFFRLR\nFBFBBFFRRL\n").unwrap();
+        assert_eq!(Day5::part1(&ids), Answer::Number(358));
+        assert_eq!(Day5::part2(&ids), Answer::Unsolved);
+        assert_eq!(Day5::part1(&Day5::parse("").unwrap()), Answer::Unsolved);
+    }
+}
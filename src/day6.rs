@@ -0,0 +1,92 @@
+//! Day 6: Custom Customs. Count the questions to which groups of passengers answered yes.
+
+use std::collections::HashSet;
+
+use crate::input::records;
+use crate::{Answer, ParseError, Solution};
+
+/// Each group's answers, read with `parse_group_answers`.
+pub fn parse_answers(
+    input: &str,
+    parse_group_answers: fn(&[&str]) -> HashSet<char>,
+) -> Vec<HashSet<char>> {
+    records(input).map(|g| parse_group_answers(&g)).collect()
+}
+
+/// The questions to which anyone in the group answered yes.
+pub fn parse_group_answers_union(group: &[&str]) -> HashSet<char> {
+    let mut answers = HashSet::new();
+    for line in group {
+        for char in line.chars() {
+            if char != ' ' {
+                answers.insert(char);
+            }
+        }
+    }
+    answers
+}
+
+/// The questions to which everyone in the group answered yes.
+pub fn parse_group_answers_intersection(group: &[&str]) -> HashSet<char> {
+    let get_answers = |line: &str| line.chars().filter(|&c| c != ' ').collect::<HashSet<_>>();
+    group
+        .iter()
+        .map(|line| get_answers(line))
+        .reduce(|everyone, person| everyone.intersection(&person).copied().collect())
+        .unwrap_or_default()
+}
+
+/// The number of questions per group, read with `parse_group_answers`, summed over all groups.
+pub fn sum_of_answers(input: &str, parse_group_answers: fn(&[&str]) -> HashSet<char>) -> usize {
+    parse_answers(input, parse_group_answers)
+        .iter()
+        .map(HashSet::len)
+        .sum()
+}
+
+/// The puzzle input is groups of answers, one person per line, separated by blank lines.
+pub struct Day6;
+
+impl Solution for Day6 {
+    // Each part reads the groups' answers differently, so there's nothing to do up front.
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        let question = |c: char| c.is_ascii_lowercase() || c.is_whitespace();
+        if let Some((i, c)) = input.char_indices().find(|&(_, c)| !question(c)) {
+            let found = &input[i..i + c.len_utf8()];
+            return Err(ParseError::at(input, found, "a question from a to z"));
+        }
+        Ok(input.to_owned())
+    }
+
+    fn part1(input: &Self::Parsed) -> Answer {
+        sum_of_answers(input, parse_group_answers_union).into()
+    }
+
+    fn part2(input: &Self::Parsed) -> Answer {
+        sum_of_answers(input, parse_group_answers_intersection).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn example() {
+        let input = examples::text(6, "example.txt");
+        let answers = parse_answers(&input, parse_group_answers_union);
+        assert_eq!(
+            answers.iter().map(HashSet::len).collect::<Vec<usize>>(),
+            vec![3, 3, 3, 1, 1]
+        );
+
+        let answers = parse_answers(&input, parse_group_answers_intersection);
+        assert_eq!(
+            answers.iter().map(HashSet::len).collect::<Vec<usize>>(),
+            vec![3, 0, 1, 1, 1]
+        );
+    }
+}
@@ -0,0 +1,165 @@
+//! Day 7: Handy Haversacks. Follow the rules about which bags must contain which other bags.
+
+use petgraph::visit::DfsEvent::TreeEdge;
+use petgraph::visit::{depth_first_search, DfsPostOrder, Walker};
+
+use petgraph::graphmap::{GraphMap, NodeTrait};
+use petgraph::Directed;
+use std::collections::HashMap;
+
+use crate::input::parse_lines;
+use crate::scan::Scanner;
+use crate::{Answer, ParseError, Solution};
+
+/// Bag colours, with an edge from each bag to the bags it must contain, weighted by how many.
+pub type Graph<'a> = GraphMap<&'a str, u32, Directed>;
+
+/// A rule saying how many bags of which colours a bag of one colour must contain.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Rule {
+    pub color: String,
+    pub contents: Vec<(u32, String)>,
+}
+
+impl Rule {
+    /// Reads a rule like `light red bags contain 1 bright white bag, 2 muted yellow bags.`
+    pub fn parse(line: &str) -> Result<Self, ParseError> {
+        let mut s = Scanner::new(line);
+        let color = s.until(" bags contain ")?.to_owned();
+        s.literal(" bags contain ")?;
+        let mut contents = Vec::new();
+        if s.literal("no other bags.").is_ok() {
+            s.end()?;
+            return Ok(Self { color, contents });
+        }
+        loop {
+            let count = s.number()?;
+            s.literal(" ")?;
+            let inner = s.until(" bag")?;
+            s.literal(" bag")?;
+            let _plural = s.literal("s");
+            contents.push((count, inner.to_owned()));
+            if s.literal(", ").is_err() {
+                s.literal(".").map_err(|e| e.expecting("\", \" or \".\""))?;
+                s.end()?;
+                return Ok(Self { color, contents });
+            }
+        }
+    }
+}
+
+/// The graph of rules, borrowing the colours from them.
+pub fn parse_rules(rules: &[Rule]) -> Graph<'_> {
+    let mut g = GraphMap::new();
+    for rule in rules {
+        // Ensure we add the node, even if it has no contents.
+        g.add_node(rule.color.as_str());
+        for (count, color) in &rule.contents {
+            g.add_edge(rule.color.as_str(), color.as_str(), *count);
+        }
+    }
+    g
+}
+
+// On futher searching I found there's an adapter
+// petgraph::algo::Reversed
+// which might do what this does. Oh well, I've written it now.
+/// The same graph, with every edge turned around: from each bag to the bags which contain it.
+pub fn reversed<N, E>(src: &GraphMap<N, E, Directed>) -> GraphMap<N, E, Directed>
+where
+    N: NodeTrait,
+    E: Copy,
+{
+    let mut g = GraphMap::new();
+    for (start, end, &weight) in src.all_edges() {
+        g.add_edge(end, start, weight);
+    }
+    g
+}
+
+/// Every bag which ends up containing a shiny gold bag, given the reversed graph.
+pub fn shiny_gold_containers<'a>(contained_in: &'a Graph) -> Vec<&'a str> {
+    let mut containers = Vec::new();
+    depth_first_search(contained_in, Some("shiny gold"), |event| {
+        if let TreeEdge(_, b) = event {
+            containers.push(b);
+        }
+    });
+    containers
+}
+
+/// How many bags a shiny gold bag ends up containing.
+pub fn shiny_gold_contents(contains: &Graph) -> u32 {
+    let mut bags_inside = HashMap::new();
+    let dfs_postorder = DfsPostOrder::new(contains, "shiny gold");
+    for node in dfs_postorder.iter(contains) {
+        let count = contains
+            .edges(node)
+            .map(|(_, dest, weight)| weight * (1 + bags_inside.get(dest).unwrap()))
+            .sum();
+        bags_inside.insert(node, count);
+    }
+    bags_inside["shiny gold"]
+}
+
+/// The puzzle input is one rule per line.
+pub struct Day7;
+
+impl Solution for Day7 {
+    // The graph borrows the bag colours, so hang on to the rules and build it in each part.
+    type Parsed = Vec<Rule>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input, Rule::parse)
+    }
+
+    fn part1(rules: &Self::Parsed) -> Answer {
+        let containers = parse_rules(rules);
+        let contained_in = reversed(&containers);
+        shiny_gold_containers(&contained_in).len().into()
+    }
+
+    fn part2(rules: &Self::Parsed) -> Answer {
+        let containers = parse_rules(rules);
+        shiny_gold_contents(&containers).into()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn example() {
+        let rules = Day7::parse(&examples::text(7, "example.txt")).unwrap();
+        let containers = parse_rules(&rules);
+        let contained_in = reversed(&containers);
+        let mut gold_containers = shiny_gold_containers(&contained_in);
+        gold_containers.sort();
+        assert_eq!(
+            gold_containers,
+            vec!["bright white", "dark orange", "light red", "muted yellow"]
+        );
+
+        let gold_contents = shiny_gold_contents(&containers);
+        assert_eq!(gold_contents, 32);
+    }
+
+    #[test]
+    fn rules() {
+        assert_eq!(
+            Rule::parse("bright white bags contain 1 shiny gold bag, 22 dark olive bags.").unwrap(),
+            Rule {
+                color: "bright white".to_owned(),
+                contents: vec![(1, "shiny gold".to_owned()), (22, "dark olive".to_owned())],
+            }
+        );
+        let error = Rule::parse("faded blue bags contain some bags.")
+            .err()
+            .unwrap();
+        assert_eq!((error.line, error.column), (1, 25));
+        assert!(Rule::parse("bright white bags contain 1 shiny gold bag").is_err());
+        assert!(Rule::parse("bright white bag contains no other bags.").is_err());
+    }
+}
@@ -0,0 +1,182 @@
+//! Day 8: Handheld Halting. Run a boot program with three instructions, find out where it starts
+//! looping, and fix it so that it doesn't.
+
+use crate::input::parse_lines;
+use crate::scan::Scanner;
+use crate::{Answer, ParseError, Solution};
+use std::collections::HashSet;
+
+/// One line of a boot program.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Instruction {
+    /// `acc`: add to the accumulator.
+    Accumulate(isize),
+    /// `jmp`: move the instruction pointer by this much.
+    Jump(isize),
+    /// `nop`: do nothing. The value is only there for a `jmp` to use if it's swapped in.
+    NoOp(isize),
+}
+
+impl Instruction {
+    /// Reads a line like `jmp -4`.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut s = Scanner::new(input);
+        let start = s.clone();
+        let instruction: fn(isize) -> Self = match s.take_while(|c| c.is_ascii_alphabetic()) {
+            "acc" => Self::Accumulate,
+            "jmp" => Self::Jump,
+            "nop" => Self::NoOp,
+            _ => return Err(start.error("acc, jmp or nop")),
+        };
+        s.literal(" ")?;
+        let value = s.number()?;
+        s.end()?;
+        Ok(instruction(value))
+    }
+}
+
+/// A boot program part of the way through running. Every instruction it has run is remembered, so
+/// that it can tell when it starts to repeat itself.
+pub struct Execution {
+    ip: usize,
+    acc: isize,
+    program: Vec<Instruction>,
+    ip_history: HashSet<usize>,
+}
+
+impl Execution {
+    /// Reads a program, one instruction per line, and gets ready to run it.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self::new(parse_lines(input, Instruction::parse)?))
+    }
+
+    /// Gets ready to run `program` from the first instruction, with the accumulator at zero.
+    pub fn new(program: Vec<Instruction>) -> Self {
+        Self {
+            ip: 0,
+            acc: 0,
+            program,
+            ip_history: HashSet::new(),
+        }
+    }
+
+    pub fn accumulator(&self) -> isize {
+        self.acc
+    }
+
+    /// The index of the next instruction to run.
+    pub fn instruction_pointer(&self) -> usize {
+        self.ip
+    }
+
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+}
+
+impl Execution {
+    /// Runs the next instruction. This panics if the program has already finished.
+    pub fn step_once(&mut self) {
+        self.ip_history.insert(self.ip);
+        match self.program[self.ip] {
+            Instruction::Accumulate(delta) => {
+                self.acc += delta;
+                self.ip += 1;
+            }
+            Instruction::Jump(delta) => {
+                let next = (self.ip as isize) + delta;
+                self.ip = next as usize;
+            }
+            Instruction::NoOp(_) => self.ip += 1,
+        }
+    }
+}
+
+#[derive(Debug, Eq, PartialEq)]
+pub enum ProgramOutcome {
+    /// The program was about to run an instruction for the second time.
+    InfiniteLoop,
+    /// The program went past its last instruction.
+    Terminated,
+}
+
+/// Runs the program until it either finishes or starts to loop.
+pub fn run(state: &mut Execution) -> ProgramOutcome {
+    loop {
+        // Blurb says "immediately after", but let's err on caution and choose >=
+        if state.ip >= state.program.len() {
+            return ProgramOutcome::Terminated;
+        } else if state.ip_history.contains(&state.ip) {
+            return ProgramOutcome::InfiniteLoop;
+        } else {
+            state.step_once();
+        }
+    }
+}
+
+/// Swaps a single `jmp` for a `nop` or the other way round so that the program finishes, and gives
+/// the finished execution.
+pub fn find_fixed(program: &[Instruction]) -> Option<Execution> {
+    for (index, instruction) in program.iter().enumerate() {
+        let mut altered = program.to_vec();
+        match *instruction {
+            Instruction::Accumulate(_) => continue,
+            Instruction::Jump(value) => altered[index] = Instruction::NoOp(value),
+            Instruction::NoOp(value) => altered[index] = Instruction::Jump(value),
+        }
+        let mut execution = Execution::new(altered);
+        match run(&mut execution) {
+            ProgramOutcome::InfiniteLoop => continue,
+            ProgramOutcome::Terminated => return Some(execution),
+        }
+    }
+    None
+}
+
+/// The puzzle input is the boot program.
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed = Execution;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        Execution::parse(input)
+    }
+
+    fn part1(start: &Self::Parsed) -> Answer {
+        let mut execution = Execution::new(start.program.clone());
+        run(&mut execution);
+        execution.acc.into()
+    }
+
+    fn part2(start: &Self::Parsed) -> Answer {
+        match find_fixed(&start.program) {
+            Some(fixed) => fixed.acc.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    #[test]
+    fn example() {
+        let mut execution = Execution::parse(&examples::text(8, "example.txt")).unwrap();
+        let outcome = run(&mut execution);
+        assert_eq!(outcome, ProgramOutcome::InfiniteLoop);
+        assert_eq!(execution.acc, 5);
+
+        let fixed = find_fixed(&execution.program).unwrap();
+        assert_eq!(fixed.acc, 8);
+    }
+
+    #[test]
+    fn unfixable() {
+        // Swapping the only instruction for a jmp +0 loops forever.
+        let start = Day8::parse("nop +0\n").unwrap();
+        assert_eq!(Day8::part2(&start), Answer::Unsolved);
+    }
+}
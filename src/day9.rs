@@ -1,20 +1,42 @@
+//! Day 9: Encoding Error. Find the number in an XMAS stream which isn't a sum of two of the
+//! numbers just before it, and the run of numbers which adds up to it.
+
+use crate::input::parse_lines;
+use crate::scan::number_line;
+use crate::{Answer, ParseError, Solution};
 use std::collections::HashMap;
-use std::str::FromStr;
 
-fn main() {
-    let numbers: Vec<_> = aoc_2020::problem_lines()
-        .map(|s| usize::from_str(&s).unwrap())
-        .collect();
-    let invalid = find_invalid_number(&numbers, 25).unwrap();
-    println!("{}", invalid);
-    let subseq = find_subsequence_with_sum(&numbers, invalid).unwrap();
-    println!(
-        "{}",
-        subseq.iter().min().unwrap() + subseq.iter().max().unwrap()
-    );
+/// The puzzle input is one number per line. Each number after the preamble should be a sum of two
+/// of the `PREAMBLE` numbers before it; the puzzle's preamble is 25, but its example's is 5.
+pub struct Day9<const PREAMBLE: usize = 25>;
+
+impl<const PREAMBLE: usize> Solution for Day9<PREAMBLE> {
+    type Parsed = Vec<usize>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        parse_lines(input, number_line)
+    }
+
+    fn part1(numbers: &Self::Parsed) -> Answer {
+        match find_invalid_number(numbers, PREAMBLE) {
+            Some(invalid) => invalid.into(),
+            None => Answer::Unsolved,
+        }
+    }
+
+    fn part2(numbers: &Self::Parsed) -> Answer {
+        let subseq = find_invalid_number(numbers, PREAMBLE)
+            .and_then(|invalid| find_subsequence_with_sum(numbers, invalid));
+        match subseq.and_then(|s| Some(s.iter().min()? + s.iter().max()?)) {
+            Some(weakness) => weakness.into(),
+            None => Answer::Unsolved,
+        }
+    }
 }
 
-fn find_invalid_number(input: &[usize], window_size: usize) -> Option<usize> {
+/// The first number after the preamble which isn't the sum of two different numbers among the
+/// `window_size` before it.
+pub fn find_invalid_number(input: &[usize], window_size: usize) -> Option<usize> {
     if input.len() <= window_size {
         return None;
     }
@@ -45,10 +67,11 @@ fn find_invalid_number(input: &[usize], window_size: usize) -> Option<usize> {
     None
 }
 
-fn find_subsequence_with_sum(input: &[usize], target: usize) -> Option<&[usize]> {
+/// A run of at least two consecutive numbers which adds up to `target`.
+pub fn find_subsequence_with_sum(input: &[usize], target: usize) -> Option<&[usize]> {
     // There are 2 ** n subsets to worry about, but only O(n^2) (nth triangle number)
     // subsequences to worry about. So sod it, let's try all n(n+1)/2 of them.
-    for i in 0..(input.len() - 1) {
+    for i in 0..input.len().saturating_sub(1) {
         // Loop through the subsequences starting at index i
         let mut sum = input[i];
         for j in (i + 1)..(input.len()) {
@@ -68,6 +91,7 @@ fn find_subsequence_with_sum(input: &[usize], target: usize) -> Option<&[usize]>
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::examples;
 
     #[test]
     fn test_example_1() {
@@ -100,14 +124,21 @@ mod test {
 
     #[test]
     fn example_window_5() {
-        let numbers = [
-            35, 20, 15, 25, 47, 40, 62, 55, 65, 95, 102, 117, 150, 182, 127, 219, 299, 277, 309,
-            576,
-        ];
+        let numbers = Day9::<5>::parse(&examples::text(9, "example.txt")).unwrap();
+        assert_eq!(Day9::<5>::part1(&numbers), Answer::Number(127));
+        assert_eq!(Day9::<5>::part2(&numbers), Answer::Number(62));
         assert_eq!(find_invalid_number(&numbers, 5), Some(127));
         assert_eq!(
             find_subsequence_with_sum(&numbers, 127),
             Some(&numbers[2..6])
         );
     }
+
+    #[test]
+    fn too_short_for_the_preamble() {
+        let numbers = <Day9>::parse("1\n2\n3\n").unwrap();
+        assert_eq!(<Day9>::part1(&numbers), Answer::Unsolved);
+        assert_eq!(<Day9>::part2(&numbers), Answer::Unsolved);
+        assert_eq!(find_subsequence_with_sum(&[], 3), None);
+    }
 }
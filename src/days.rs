@@ -0,0 +1,157 @@
+use std::any::Any;
+use std::fmt;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::solution::{Answer, Solution};
+use crate::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input {
+            "1" => Some(Part::One),
+            "2" => Some(Part::Two),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Part::One => f.pad("1"),
+            Part::Two => f.pad("2"),
+        }
+    }
+}
+
+impl Serialize for Part {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.serialize_u8(match self {
+            Part::One => 1,
+            Part::Two => 2,
+        })
+    }
+}
+
+impl<'de> Deserialize<'de> for Part {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(Part::One),
+            2 => Ok(Part::Two),
+            n => Err(serde::de::Error::custom(format!("there's no part {}", n))),
+        }
+    }
+}
+
+pub const PARTS: [Part; 2] = [Part::One, Part::Two];
+
+/// The output of a day's parser, with its type erased so that every day looks the same from the
+/// outside. Only the day which produced it can make sense of it.
+pub struct Parsed(Box<dyn Any>);
+
+/// A puzzle in the registry.
+pub struct Day {
+    pub number: usize,
+    parse: fn(&str) -> Result<Parsed>,
+    part1: fn(&Parsed) -> Answer,
+    part2: fn(&Parsed) -> Answer,
+}
+
+fn parse<S: Solution>(input: &str) -> Result<Parsed> {
+    Ok(Parsed(Box::new(S::parse(input)?)))
+}
+
+fn downcast<S: Solution>(parsed: &Parsed) -> &S::Parsed {
+    parsed
+        .0
+        .downcast_ref()
+        .expect("parsed input belongs to a different day")
+}
+
+fn part1<S: Solution>(parsed: &Parsed) -> Answer {
+    S::part1(downcast::<S>(parsed))
+}
+
+fn part2<S: Solution>(parsed: &Parsed) -> Answer {
+    S::part2(downcast::<S>(parsed))
+}
+
+impl Day {
+    pub(crate) const fn new<S: Solution>(number: usize) -> Self {
+        Self {
+            number,
+            parse: parse::<S>,
+            part1: part1::<S>,
+            part2: part2::<S>,
+        }
+    }
+
+    pub fn parse(&self, input: &str) -> Result<Parsed> {
+        (self.parse)(input)
+    }
+
+    pub fn solve(&self, part: Part, parsed: &Parsed) -> Answer {
+        match part {
+            Part::One => (self.part1)(parsed),
+            Part::Two => (self.part2)(parsed),
+        }
+    }
+
+    /// Parse the input and solve the given part in one go.
+    pub fn run(&self, part: Part, input: &str) -> Result<Answer> {
+        Ok(self.solve(part, &self.parse(input)?))
+    }
+}
+
+pub const DAYS: [Day; 20] = [
+    Day::new::<day1::Day1>(1),
+    Day::new::<day2::Day2>(2),
+    Day::new::<day3::Day3>(3),
+    Day::new::<day4::Day4>(4),
+    Day::new::<day5::Day5>(5),
+    Day::new::<day6::Day6>(6),
+    Day::new::<day7::Day7>(7),
+    Day::new::<day8::Day8>(8),
+    Day::new::<day9::Day9>(9),
+    Day::new::<day10::Day10>(10),
+    Day::new::<day11::Day11>(11),
+    Day::new::<day12::Day12>(12),
+    Day::new::<day13::Day13>(13),
+    Day::new::<day14::Day14>(14),
+    Day::new::<day15::Day15>(15),
+    Day::new::<day16::Day16>(16),
+    Day::new::<day17::Day17>(17),
+    Day::new::<day18::Day18>(18),
+    Day::new::<day19::Day19>(19),
+    Day::new::<day20::Day20>(20),
+];
+
+pub fn find(number: usize) -> Option<&'static Day> {
+    DAYS.iter().find(|d| d.number == number)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn registry_is_in_order() {
+        let numbers: Vec<_> = DAYS.iter().map(|d| d.number).collect();
+        assert_eq!(numbers, (1..=20).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn run_through_registry() {
+        let day = find(1).unwrap();
+        let parsed = day.parse("1721\n979\n366\n299\n675\n1456\n").unwrap();
+        assert_eq!(day.solve(Part::One, &parsed), Answer::Number(514579));
+        assert_eq!(day.solve(Part::Two, &parsed), Answer::Number(241861950));
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Everything that can go wrong while fetching or parsing a puzzle's input.
+#[derive(Debug)]
+pub enum Error {
+    MissingInput {
+        path: PathBuf,
+    },
+    Io {
+        path: PathBuf,
+        source: io::Error,
+    },
+    Encoding {
+        path: PathBuf,
+        valid_up_to: usize,
+    },
+    /// A puzzle input which a day's parser couldn't make sense of.
+    Malformed(ParseError),
+    /// Any other file we couldn't make sense of.
+    Parse(String),
+    /// Talking to the puzzle server needs a session cookie, and we haven't got one.
+    NoSession,
+    /// The puzzle server couldn't be reached, or didn't like what we asked for.
+    Http {
+        url: String,
+        reason: String,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    pub fn path(&self) -> Option<&Path> {
+        match self {
+            Error::MissingInput { path } => Some(path),
+            Error::Io { path, .. } => Some(path),
+            Error::Encoding { path, .. } => Some(path),
+            Error::Malformed(_) | Error::Parse(_) | Error::NoSession | Error::Http { .. } => None,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::MissingInput { path } => write!(
+                f,
+                "no input found at {}; save the puzzle input there (aoc fetch will \
+                download it), or point --input or $AOC_INPUT somewhere else",
+                path.display()
+            ),
+            Error::Io { path, source } => write!(f, "couldn't read {}: {}", path.display(), source),
+            Error::Encoding { path, valid_up_to } => write!(
+                f,
+                "{} isn't valid UTF-8 (first bad byte at offset {})",
+                path.display(),
+                valid_up_to
+            ),
+            Error::Malformed(e) => write!(f, "malformed input at {}", e),
+            Error::Parse(message) => write!(f, "malformed input: {}", message),
+            Error::NoSession => write!(
+                f,
+                "no session cookie; set ${} to the value of the session cookie \
+                your browser has for the puzzle site",
+                crate::client::SESSION_VAR
+            ),
+            Error::Http { url, reason } => write!(f, "couldn't fetch {}: {}", url, reason),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(e: ParseError) -> Self {
+        Error::Malformed(e)
+    }
+}
+
+/// Exactly where, and how, an input stopped making sense. Lines and columns count from 1, and
+/// columns count characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    /// The offending text; empty if the input ended too soon.
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+
+    /// Complain about `part`, which must be a slice of `input`; its position is worked out from
+    /// where it sits. Anything else is blamed on the end of the input.
+    pub fn at(input: &str, part: &str, expected: impl Into<String>) -> Self {
+        let (line, column) = position(input, part);
+        let found = part.lines().next().unwrap_or_default();
+        Self::new(line, column, expected, found)
+    }
+
+    /// Complain that `input` stopped short.
+    pub fn end(input: &str, expected: impl Into<String>) -> Self {
+        Self::at(input, &input[input.len()..], expected)
+    }
+
+    /// Replace the description of what was expected.
+    pub fn expecting(self, expected: impl Into<String>) -> Self {
+        Self {
+            expected: expected.into(),
+            ..self
+        }
+    }
+
+    /// Move an error found in `part`, a slice of `input`, so that its position is relative to
+    /// `input` instead.
+    pub fn within(self, input: &str, part: &str) -> Self {
+        let (line, column) = position(input, part);
+        Self {
+            line: line + self.line - 1,
+            column: if self.line == 1 {
+                column + self.column - 1
+            } else {
+                self.column
+            },
+            ..self
+        }
+    }
+}
+
+fn position(input: &str, part: &str) -> (usize, usize) {
+    let offset = (part.as_ptr() as usize).wrapping_sub(input.as_ptr() as usize);
+    let offset = if offset <= input.len() && input.is_char_boundary(offset) {
+        offset
+    } else {
+        input.len()
+    };
+    let before = &input[..offset];
+    let line = before.matches('\n').count() + 1;
+    let column = before
+        .rsplit('\n')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .count()
+        + 1;
+    (line, column)
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        match self.found.as_str() {
+            "" => f.write_str("nothing"),
+            found => write!(f, "{:?}", found),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn positions() {
+        let input = "first\nsecond line\nthïrd x";
+        let x = &input[input.len() - 1..];
+        let e = ParseError::at(input, x, "a number");
+        assert_eq!((e.line, e.column, e.found.as_str()), (3, 7, "x"));
+        assert_eq!(
+            e.to_string(),
+            "line 3, column 7: expected a number, found \"x\""
+        );
+
+        let second = input.lines().nth(1).unwrap();
+        let e = ParseError::at(second, &second[7..], "a word").within(input, second);
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 8, "line"));
+
+        let e = ParseError::end(input, "more");
+        assert_eq!((e.line, e.column), (3, 8));
+        assert!(e.to_string().ends_with("found nothing"));
+
+        // Not part of the input at all, so the end gets the blame.
+        assert_eq!(ParseError::at(input, "x", "?").line, 3);
+    }
+}
@@ -0,0 +1,174 @@
+//! Puzzle examples kept as data rather than code. Each `examples/dayN/<name>.txt` is an input, and
+//! the `<name>.expected` beside it says what the solver should make of it:
+//!
+//! ```text
+//! # Comments and blank lines are ignored.
+//! part1: 514579
+//! part2: 241861950
+//! ```
+//!
+//! Either part may be left out, in which case it isn't run; that's handy when a part would take
+//! too long, or the example only makes sense for one part.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::days::{self, Part};
+use crate::error::{Error, Result};
+use crate::InputSource;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub day: usize,
+    pub name: String,
+    pub input: PathBuf,
+    pub expected: Vec<(Part, String)>,
+}
+
+/// A part which didn't give the expected answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Failure {
+    pub part: Part,
+    pub expected: String,
+    pub actual: String,
+}
+
+pub fn default_root() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("examples")
+}
+
+fn read_dir(path: &Path) -> Result<Vec<PathBuf>> {
+    let io_error = |source| Error::Io {
+        path: path.to_owned(),
+        source,
+    };
+    let mut paths = Vec::new();
+    for entry in fs::read_dir(path).map_err(io_error)? {
+        paths.push(entry.map_err(io_error)?.path());
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+/// Find every example under `root`, ordered by day and then by name. An input without an
+/// `.expected` file is a mistake, so it's an error rather than something to skip.
+pub fn discover(root: &Path) -> Result<Vec<Example>> {
+    let mut examples = Vec::new();
+    for dir in read_dir(root)? {
+        let day = match dir
+            .file_name()
+            .and_then(|n| n.to_str())
+            .and_then(|n| n.strip_prefix("day"))
+            .and_then(|n| n.parse().ok())
+        {
+            Some(day) if dir.is_dir() => day,
+            _ => continue,
+        };
+        for input in read_dir(&dir)? {
+            if input.extension().is_none_or(|e| e != "txt") {
+                continue;
+            }
+            let expected = input.with_extension("expected");
+            let text = fs::read_to_string(&expected).map_err(|source| Error::Io {
+                path: expected.clone(),
+                source,
+            })?;
+            let expected = parse_expected(&text)
+                .map_err(|e| Error::Parse(format!("{}: {}", expected.display(), e)))?;
+            examples.push(Example {
+                day,
+                name: input.file_stem().unwrap().to_string_lossy().into_owned(),
+                input,
+                expected,
+            });
+        }
+    }
+    examples.sort_by_key(|e| e.day);
+    Ok(examples)
+}
+
+fn parse_expected(text: &str) -> std::result::Result<Vec<(Part, String)>, String> {
+    let mut expected = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let (part, answer) = match scan_fmt!(line, "part{d}: {/.+/}", String, String) {
+            Ok((part, answer)) => (Part::parse(&part), answer),
+            Err(_) => (None, String::new()),
+        };
+        match part {
+            Some(part) => expected.push((part, answer.trim().to_owned())),
+            None => return Err(format!("expected part1: or part2:, got {:?}", line)),
+        }
+    }
+    Ok(expected)
+}
+
+/// The text of `file` in day `day`'s examples, for tests which look at more than the answers.
+#[cfg(test)]
+pub(crate) fn text(day: usize, file: &str) -> String {
+    let path = default_root().join(format!("day{}", day)).join(file);
+    fs::read_to_string(&path).unwrap_or_else(|e| panic!("{}: {}", path.display(), e))
+}
+
+impl Example {
+    /// Run the example through its day's solver, returning the parts which got it wrong.
+    pub fn run(&self) -> Result<Vec<Failure>> {
+        let day = days::find(self.day)
+            .ok_or_else(|| Error::Parse(format!("there's no day {}", self.day)))?;
+        let parsed = day.parse(&InputSource::Path(self.input.clone()).try_content()?)?;
+        let mut failures = Vec::new();
+        for (part, expected) in &self.expected {
+            let actual = day.solve(*part, &parsed).to_string();
+            if &actual != expected {
+                failures.push(Failure {
+                    part: *part,
+                    expected: expected.clone(),
+                    actual,
+                });
+            }
+        }
+        Ok(failures)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn expected_files() {
+        let parsed = parse_expected("# day 1\npart1: 514579\n\npart2: 241861950\n").unwrap();
+        assert_eq!(
+            parsed,
+            vec![
+                (Part::One, "514579".to_owned()),
+                (Part::Two, "241861950".to_owned())
+            ]
+        );
+        assert!(parse_expected("part3: 1").is_err());
+        assert!(parse_expected("514579").is_err());
+    }
+
+    #[test]
+    fn all_examples() {
+        let examples = discover(&default_root()).unwrap();
+        assert!(!examples.is_empty());
+        let mut problems = Vec::new();
+        for example in &examples {
+            match example.run() {
+                Ok(failures) => {
+                    for f in failures {
+                        problems.push(format!(
+                            "day {} {} part {}: expected {}, got {}",
+                            example.day, example.name, f.part, f.expected, f.actual
+                        ));
+                    }
+                }
+                Err(e) => problems.push(format!("day {} {}: {}", example.day, example.name, e)),
+            }
+        }
+        assert!(problems.is_empty(), "\n{}", problems.join("\n"));
+    }
+}
@@ -0,0 +1,1532 @@
+//! Random puzzle inputs, for finding out how the solvers cope with inputs bigger or stranger than
+//! the real ones. Every generator is seeded, so the same seed always makes the same input, and
+//! takes a size: a number of lines, tiles, or whatever suits the day.
+//!
+//! In `Mode::Known` a generator also says what the answers are. Where it can, it builds the input
+//! around answers it chose; otherwise it works them out the slow, obvious way, which checks the
+//! solver rather than repeating it. Known inputs may be more constrained than random ones, so that
+//! there's exactly one answer to find.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use itertools::Itertools;
+use rand::seq::{index, SliceRandom};
+use rand::{Rng, SeedableRng};
+
+use crate::days::Part;
+use crate::grid::Grid;
+
+pub type Prng = rand_pcg::Pcg64;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Random,
+    /// Also work out the answers.
+    Known,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Generated {
+    pub input: String,
+    /// The answers, in `Mode::Known`. A part is left out if working it out would take too long.
+    pub expected: Vec<(Part, String)>,
+}
+
+impl Generated {
+    /// `answers` is only called in `Mode::Known`, since it may take a while.
+    fn new(input: String, mode: Mode, answers: impl FnOnce() -> Vec<(Part, String)>) -> Self {
+        let expected = match mode {
+            Mode::Random => Vec::new(),
+            Mode::Known => answers(),
+        };
+        Self { input, expected }
+    }
+
+    /// The answers in the format of an example's `.expected` file.
+    pub fn expected_file(&self) -> String {
+        let mut out = String::new();
+        for (part, answer) in &self.expected {
+            writeln!(out, "part{}: {}", part, answer).unwrap();
+        }
+        out
+    }
+}
+
+fn both(part1: impl ToString, part2: impl ToString) -> Vec<(Part, String)> {
+    vec![
+        (Part::One, part1.to_string()),
+        (Part::Two, part2.to_string()),
+    ]
+}
+
+/// One day's generator.
+pub struct Generator {
+    pub day: usize,
+    /// What the size counts, in the plural.
+    pub unit: &'static str,
+    pub sizes: RangeInclusive<usize>,
+    /// About the size of a real input.
+    pub default_size: usize,
+    generate: fn(&mut Prng, usize, Mode) -> Generated,
+}
+
+impl Generator {
+    /// Panics if `size` isn't one of `self.sizes`.
+    pub fn generate(&self, seed: u64, size: usize, mode: Mode) -> Generated {
+        assert!(
+            self.sizes.contains(&size),
+            "day {} can't make an input of {} {}",
+            self.day,
+            size,
+            self.unit
+        );
+        (self.generate)(&mut Prng::seed_from_u64(seed), size, mode)
+    }
+}
+
+pub static GENERATORS: [Generator; 20] = [
+    Generator {
+        day: 1,
+        unit: "entries",
+        sizes: 5..=900,
+        default_size: 200,
+        generate: day1,
+    },
+    Generator {
+        day: 2,
+        unit: "passwords",
+        sizes: 1..=100_000,
+        default_size: 1000,
+        generate: day2,
+    },
+    Generator {
+        day: 3,
+        unit: "rows",
+        sizes: 1..=100_000,
+        default_size: 323,
+        generate: day3,
+    },
+    Generator {
+        day: 4,
+        unit: "passports",
+        sizes: 1..=100_000,
+        default_size: 290,
+        generate: day4,
+    },
+    Generator {
+        day: 5,
+        unit: "boarding passes",
+        sizes: 2..=1000,
+        default_size: 800,
+        generate: day5,
+    },
+    Generator {
+        day: 6,
+        unit: "groups",
+        sizes: 1..=100_000,
+        default_size: 490,
+        generate: day6,
+    },
+    Generator {
+        day: 7,
+        unit: "bags",
+        sizes: 1..=BAG_ADJECTIVES.len() * BAG_COLOURS.len(),
+        default_size: 594,
+        generate: day7,
+    },
+    Generator {
+        day: 8,
+        unit: "instructions",
+        sizes: 2..=10_000,
+        default_size: 620,
+        generate: day8,
+    },
+    Generator {
+        day: 9,
+        unit: "numbers",
+        sizes: 30..=1000,
+        default_size: 1000,
+        generate: day9,
+    },
+    Generator {
+        day: 10,
+        unit: "adapters",
+        sizes: 1..=100_000,
+        default_size: 100,
+        generate: day10,
+    },
+    Generator {
+        day: 11,
+        unit: "rows and columns",
+        sizes: 1..=500,
+        default_size: 95,
+        generate: day11,
+    },
+    Generator {
+        day: 12,
+        unit: "instructions",
+        sizes: 1..=100_000,
+        default_size: 780,
+        generate: day12,
+    },
+    Generator {
+        day: 13,
+        unit: "buses",
+        sizes: 1..=12,
+        default_size: 9,
+        generate: day13,
+    },
+    Generator {
+        day: 14,
+        unit: "writes",
+        sizes: 1..=100_000,
+        default_size: 450,
+        generate: day14,
+    },
+    Generator {
+        day: 15,
+        unit: "starting numbers",
+        sizes: 1..=100,
+        default_size: 7,
+        generate: day15,
+    },
+    Generator {
+        day: 16,
+        unit: "fields",
+        sizes: 2..=FIELDS.len(),
+        default_size: 20,
+        generate: day16,
+    },
+    Generator {
+        day: 17,
+        unit: "rows and columns",
+        sizes: 1..=32,
+        default_size: 8,
+        generate: day17,
+    },
+    Generator {
+        day: 18,
+        unit: "expressions",
+        sizes: 1..=100_000,
+        default_size: 380,
+        generate: day18,
+    },
+    Generator {
+        day: 19,
+        unit: "messages",
+        sizes: 1..=100_000,
+        default_size: 470,
+        generate: day19,
+    },
+    Generator {
+        day: 20,
+        unit: "tiles along each side",
+        sizes: 2..=12,
+        default_size: 12,
+        generate: day20,
+    },
+];
+
+pub fn find(day: usize) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.day == day)
+}
+
+fn lines<T: ToString>(items: impl IntoIterator<Item = T>) -> String {
+    let mut out = String::new();
+    for item in items {
+        out += &item.to_string();
+        out.push('\n');
+    }
+    out
+}
+
+/// A pair and a triple summing to 2020. All the other entries are over half of that, so that no
+/// other pair or triple can, at least in `Mode::Known`.
+fn day1(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    const TARGET: u32 = 2020;
+    loop {
+        let a = rng.gen_range(1..TARGET / 2);
+        let (b, c) = (rng.gen_range(1..TARGET / 3), rng.gen_range(1..TARGET / 2));
+        let d = TARGET - b - c;
+        if !(b < c && c < d && d < TARGET / 2) || [b, c, d].contains(&a) {
+            continue;
+        }
+        let small = [a, b, c, d];
+        let mut entries = vec![a, TARGET - a, b, c, d];
+
+        let mut unwanted: HashSet<u32> = entries.iter().copied().collect();
+        for &s in &small {
+            unwanted.insert(TARGET - s);
+            if s % 2 == 0 {
+                unwanted.insert((TARGET - s) / 2);
+            }
+            for &t in &small {
+                unwanted.insert(TARGET - s - t);
+            }
+        }
+        let fillers = match mode {
+            Mode::Random => 1..TARGET,
+            Mode::Known => TARGET / 2 + 1..TARGET,
+        };
+        let mut fillers: Vec<u32> = fillers
+            .filter(|f| mode == Mode::Random || !unwanted.contains(f))
+            .filter(|f| !entries.contains(f))
+            .collect();
+        fillers.shuffle(rng);
+        if fillers.len() < size - entries.len() {
+            continue;
+        }
+        entries.extend_from_slice(&fillers[..size - entries.len()]);
+        entries.shuffle(rng);
+
+        if mode == Mode::Known {
+            // Entries may be used twice, so allow for that too.
+            let set: HashSet<u32> = entries.iter().copied().collect();
+            let pairs = set
+                .iter()
+                .filter(|&&x| x <= TARGET / 2 && set.contains(&(TARGET - x)))
+                .count();
+            let mut triples = 0;
+            for &x in &set {
+                for &y in set.iter().filter(|&&y| y >= x && x + y < TARGET) {
+                    let z = TARGET - x - y;
+                    triples += (z >= y && set.contains(&z)) as usize;
+                }
+            }
+            if pairs != 1 || triples != 1 {
+                continue;
+            }
+        }
+        return Generated::new(lines(entries), mode, || {
+            both(a * (TARGET - a), b as u64 * c as u64 * d as u64)
+        });
+    }
+}
+
+fn letter(rng: &mut Prng) -> char {
+    rng.gen_range('a'..='z')
+}
+
+fn day2(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let (mut old, mut new) = (0, 0);
+    let mut input = String::new();
+    for _ in 0..size {
+        let required = letter(rng);
+        let len = rng.gen_range(2..=20);
+        let first = rng.gen_range(1..len);
+        let second = rng.gen_range(first + 1..=len);
+        let password: String = (0..len)
+            .map(|_| match rng.gen_bool(0.3) {
+                true => required,
+                false => letter(rng),
+            })
+            .collect();
+
+        let count = password.chars().filter(|&c| c == required).count();
+        old += (first..=second).contains(&count) as usize;
+        let at = |i: usize| password.as_bytes()[i - 1] == required as u8;
+        new += (at(first) != at(second)) as usize;
+        writeln!(input, "{}-{} {}: {}", first, second, required, password).unwrap();
+    }
+    Generated::new(input, mode, || both(old, new))
+}
+
+fn day3(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    const WIDTH: usize = 31;
+    let rows: Vec<Vec<bool>> = (0..size)
+        .map(|_| (0..WIDTH).map(|_| rng.gen_bool(0.2)).collect())
+        .collect();
+    let input = lines(rows.iter().map(|row| {
+        row.iter()
+            .map(|&t| if t { '#' } else { '.' })
+            .collect::<String>()
+    }));
+    Generated::new(input, mode, || {
+        let trees = |right: usize, down: usize| {
+            rows.iter()
+                .step_by(down)
+                .enumerate()
+                .filter(|(i, row)| row[i * right % WIDTH])
+                .count()
+        };
+        let slopes = [(1, 1), (3, 1), (5, 1), (7, 1), (1, 2)];
+        let product: usize = slopes.iter().map(|&(r, d)| trees(r, d)).product();
+        both(trees(3, 1), product)
+    })
+}
+
+const EYE_COLOURS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+/// A value for one of the required passport fields.
+fn passport_value(rng: &mut Prng, field: &str, valid: bool) -> String {
+    let year = |rng: &mut Prng, range: RangeInclusive<u32>| match valid {
+        true => rng.gen_range(range).to_string(),
+        false if rng.gen() => rng.gen_range(1900..*range.start()).to_string(),
+        false => rng.gen_range(range.end() + 1..2050).to_string(),
+    };
+    let digits = |rng: &mut Prng, n| (0..n).map(|_| rng.gen_range('0'..='9')).collect::<String>();
+    let hex = |rng: &mut Prng| {
+        (0..6)
+            .map(|_| *b"0123456789abcdef".choose(rng).unwrap() as char)
+            .collect::<String>()
+    };
+    match (field, valid) {
+        ("byr", _) => year(rng, 1920..=2002),
+        ("iyr", _) => year(rng, 2010..=2020),
+        ("eyr", _) => year(rng, 2020..=2030),
+        ("hgt", true) if rng.gen() => format!("{}cm", rng.gen_range(150..=193)),
+        ("hgt", true) => format!("{}in", rng.gen_range(59..=76)),
+        ("hgt", false) => match rng.gen_range(0..3) {
+            0 => format!("{}cm", rng.gen_range(194..=250)),
+            1 => format!("{}in", rng.gen_range(20..=58)),
+            _ => rng.gen_range(59..=193).to_string(),
+        },
+        ("hcl", true) => format!("#{}", hex(rng)),
+        ("hcl", false) if rng.gen() => hex(rng),
+        ("hcl", false) => EYE_COLOURS.choose(rng).unwrap().to_string(),
+        ("ecl", true) => EYE_COLOURS.choose(rng).unwrap().to_string(),
+        ("ecl", false) => ["xry", "gmt", "lzr", "zzz"]
+            .choose(rng)
+            .unwrap()
+            .to_string(),
+        ("pid", true) => digits(rng, 9),
+        ("pid", false) => {
+            let n = *[7, 8, 10, 11].choose(rng).unwrap();
+            digits(rng, n)
+        }
+        _ => unreachable!("{} isn't a required field", field),
+    }
+}
+
+/// Each passport is valid, missing a required field, or has one invalid value.
+fn day4(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    const REQUIRED: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+    let (mut complete, mut valid) = (0, 0);
+    let mut passports = Vec::new();
+    for _ in 0..size {
+        let mut fields: Vec<&str> = REQUIRED.to_vec();
+        let invalid = match rng.gen_range(0..3) {
+            0 => {
+                fields.remove(rng.gen_range(0..fields.len()));
+                None
+            }
+            1 => {
+                complete += 1;
+                Some(*fields.choose(rng).unwrap())
+            }
+            _ => {
+                complete += 1;
+                valid += 1;
+                None
+            }
+        };
+        let mut listing: Vec<String> = fields
+            .iter()
+            .map(|&f| format!("{}:{}", f, passport_value(rng, f, Some(f) != invalid)))
+            .collect();
+        if rng.gen() {
+            listing.push(format!("cid:{}", rng.gen_range(50..350)));
+        }
+        listing.shuffle(rng);
+        let mut passport = String::new();
+        for (i, field) in listing.iter().enumerate() {
+            if i > 0 {
+                passport.push(if rng.gen_bool(0.3) { '\n' } else { ' ' });
+            }
+            passport += field;
+        }
+        passports.push(passport);
+    }
+    Generated::new(passports.join("\n\n") + "\n", mode, || {
+        both(complete, valid)
+    })
+}
+
+/// Every seat from one id to another is taken bar one, somewhere in the middle.
+fn day5(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let first = rng.gen_range(0..=1023 - size);
+    let last = first + size;
+    let missing = rng.gen_range(first + 1..last);
+    let mut passes: Vec<String> = (first..=last)
+        .filter(|&id| id != missing)
+        .map(|id| {
+            (0..10)
+                .rev()
+                .map(|bit| match (bit >= 3, id & (1 << bit) != 0) {
+                    (true, false) => 'F',
+                    (true, true) => 'B',
+                    (false, false) => 'L',
+                    (false, true) => 'R',
+                })
+                .collect()
+        })
+        .collect();
+    passes.shuffle(rng);
+    Generated::new(lines(passes), mode, || both(last, missing))
+}
+
+fn day6(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let (mut anyone, mut everyone) = (0, 0);
+    let mut groups = Vec::new();
+    for _ in 0..size {
+        // Answers which are common in the group are likelier, so that some everyone agrees on.
+        let likely: Vec<f64> = (0..26).map(|_| rng.gen_range(0.0..1.0)).collect();
+        let people: Vec<String> = (0..rng.gen_range(1..=5))
+            .map(|_| loop {
+                let answers: String = ('a'..='z')
+                    .zip(&likely)
+                    .filter(|&(_, &p)| rng.gen_bool(p * p))
+                    .map(|(c, _)| c)
+                    .collect();
+                if !answers.is_empty() {
+                    break answers;
+                }
+            })
+            .collect();
+        anyone += ('a'..='z')
+            .filter(|&c| people.iter().any(|p| p.contains(c)))
+            .count();
+        everyone += ('a'..='z')
+            .filter(|&c| people.iter().all(|p| p.contains(c)))
+            .count();
+        groups.push(people.join("\n"));
+    }
+    Generated::new(groups.join("\n\n") + "\n", mode, || both(anyone, everyone))
+}
+
+const BAG_ADJECTIVES: [&str; 18] = [
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+
+const BAG_COLOURS: [&str; 33] = [
+    "aqua",
+    "beige",
+    "black",
+    "blue",
+    "bronze",
+    "brown",
+    "chartreuse",
+    "coral",
+    "crimson",
+    "cyan",
+    "fuchsia",
+    "gold",
+    "gray",
+    "green",
+    "indigo",
+    "lavender",
+    "lime",
+    "magenta",
+    "maroon",
+    "olive",
+    "orange",
+    "plum",
+    "purple",
+    "red",
+    "salmon",
+    "silver",
+    "tan",
+    "teal",
+    "tomato",
+    "turquoise",
+    "violet",
+    "white",
+    "yellow",
+];
+
+/// Bags are put on levels, and only contain bags on lower levels. Shiny gold is near the bottom,
+/// so that the number of bags inside it stays small enough for the solver.
+fn day7(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    const LEVELS: usize = 8;
+    const GOLD_LEVEL: usize = 4;
+    let mut names: Vec<String> = BAG_ADJECTIVES
+        .iter()
+        .flat_map(|a| BAG_COLOURS.iter().map(move |c| format!("{} {}", a, c)))
+        .filter(|name| name != "shiny gold")
+        .collect();
+    names.shuffle(rng);
+    names.truncate(size - 1);
+    names.insert(0, "shiny gold".to_owned());
+    let levels: Vec<usize> = (0..size)
+        .map(|i| match i {
+            0 => GOLD_LEVEL,
+            _ => rng.gen_range(0..LEVELS),
+        })
+        .collect();
+
+    let contents: Vec<Vec<(u32, usize)>> = (0..size)
+        .map(|bag| {
+            let below: Vec<usize> = (0..size).filter(|&b| levels[b] > levels[bag]).collect();
+            let n = rng.gen_range(0..=4).min(below.len());
+            below
+                .choose_multiple(rng, n)
+                .map(|&b| (rng.gen_range(1..=5), b))
+                .collect()
+        })
+        .collect();
+
+    let mut rules: Vec<String> = (0..size)
+        .map(|bag| {
+            let inside: Vec<String> = contents[bag]
+                .iter()
+                .map(|&(n, b)| format!("{} {} bag{}", n, names[b], if n == 1 { "" } else { "s" }))
+                .collect();
+            match inside.is_empty() {
+                true => format!("{} bags contain no other bags.", names[bag]),
+                false => format!("{} bags contain {}.", names[bag], inside.join(", ")),
+            }
+        })
+        .collect();
+    rules.shuffle(rng);
+
+    Generated::new(lines(rules), mode, || {
+        let mut holders = HashSet::new();
+        let mut queue = VecDeque::from(vec![0]);
+        while let Some(inner) = queue.pop_front() {
+            for (bag, inside) in contents.iter().enumerate() {
+                if inside.iter().any(|&(_, b)| b == inner) && holders.insert(bag) {
+                    queue.push_back(bag);
+                }
+            }
+        }
+        fn inside(bag: usize, contents: &[Vec<(u32, usize)>]) -> u32 {
+            contents[bag]
+                .iter()
+                .map(|&(n, b)| n * (1 + inside(b, contents)))
+                .sum()
+        }
+        both(holders.len(), inside(0, &contents))
+    })
+}
+
+/// A program which runs from start to end with one jmp changed to a nop: it jumps back to where
+/// it's been. Every other instruction it runs loops too if changed; the instructions it jumps
+/// over are never run.
+fn day8(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let mut program: Vec<String> = Vec::new();
+    // Where the fixed program goes, and the accumulator after each instruction there.
+    let mut path: Vec<(usize, i32)> = Vec::new();
+    let mut acc = 0;
+    while program.len() < size {
+        let here = program.len();
+        let back = |rng: &mut Prng| match path.choose(rng) {
+            Some(&(p, _)) => p as isize - here as isize,
+            None => 0,
+        };
+        if here + 2 < size && rng.gen_bool(0.15) {
+            let over = rng.gen_range(1..=4).min(size - here - 2);
+            program.push(format!("jmp {:+}", over + 1));
+            path.push((here, acc));
+            for _ in 0..over {
+                program.push(match rng.gen() {
+                    true => format!("acc {:+}", rng.gen_range(-99..=99)),
+                    false => format!("nop {:+}", rng.gen_range(-50..=50)),
+                });
+            }
+        } else if rng.gen_bool(0.3) {
+            program.push(format!("nop {:+}", back(rng)));
+            path.push((here, acc));
+        } else {
+            let delta = rng.gen_range(-99..=99);
+            acc += delta;
+            program.push(format!("acc {:+}", delta));
+            path.push((here, acc));
+        }
+    }
+
+    // Corrupt one of the nops, or an acc which then never happens.
+    let candidates: Vec<usize> = (1..path.len())
+        .filter(|&i| !program[path[i].0].starts_with("jmp"))
+        .collect();
+    let i = candidates.choose(rng).copied().unwrap_or(0);
+    let corrupt = path[i].0;
+    let before = if i == 0 { 0 } else { path[i - 1].1 };
+    let mut part2 = acc;
+    if program[corrupt].starts_with("acc") {
+        part2 -= path[i].1 - before;
+    }
+    let earlier = path[rng.gen_range(0..i.max(1))].0;
+    program[corrupt] = format!("jmp {:+}", earlier as isize - corrupt as isize);
+    Generated::new(lines(program), mode, || both(before, part2))
+}
+
+/// A number which isn't the sum of two of the 25 before it, and is the sum of a run of earlier
+/// numbers. Every other number is the sum of two of the older ones of the 25 before it, which
+/// keeps them from growing too fast.
+fn day9(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    const PREAMBLE: usize = 25;
+    'attempt: loop {
+        let mut numbers: Vec<u64> = index::sample(rng, 100, PREAMBLE)
+            .into_iter()
+            .map(|n| n as u64 + 1)
+            .collect();
+        let invalid_at = rng.gen_range(size / 2..size);
+        while numbers.len() < size {
+            let window = &numbers[numbers.len() - PREAMBLE..];
+            if numbers.len() == invalid_at {
+                let pair_sums: HashSet<u64> = (0..PREAMBLE)
+                    .flat_map(|i| (i + 1..PREAMBLE).map(move |j| window[i] + window[j]))
+                    .collect();
+                let run = (0..100).find_map(|_| {
+                    let start = rng.gen_range(0..numbers.len() - 2);
+                    let end = rng.gen_range(start + 2..=(start + 17).min(numbers.len()));
+                    let sum: u64 = numbers[start..end].iter().sum();
+                    Some(sum).filter(|sum| !pair_sums.contains(sum))
+                });
+                match run {
+                    Some(sum) => numbers.push(sum),
+                    None => continue 'attempt,
+                }
+            } else {
+                let older = &window[..10];
+                let i = rng.gen_range(0..older.len());
+                let j = (i + rng.gen_range(1..older.len())) % older.len();
+                numbers.push(older[i] + older[j]);
+            }
+        }
+        let invalid = numbers[invalid_at];
+        return Generated::new(lines(&numbers), mode, || {
+            let run = (0..numbers.len())
+                .find_map(|i| {
+                    let mut sum = numbers[i];
+                    (i + 1..numbers.len()).find_map(|j| {
+                        sum += numbers[j];
+                        Some(&numbers[i..=j]).filter(|_| sum == invalid)
+                    })
+                })
+                .unwrap();
+            both(
+                invalid,
+                run.iter().min().unwrap() + run.iter().max().unwrap(),
+            )
+        });
+    }
+}
+
+/// Adapters 1 or 3 jolts apart. A run of 1s multiplies the number of arrangements, so after a
+/// while the gaps are all 3s, to keep the number in a u64.
+fn day10(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let mut adapters = Vec::new();
+    // The number of ways of reaching the last three adapters, newest last.
+    let mut ways: VecDeque<(usize, u64)> = VecDeque::from(vec![(0, 1)]);
+    let (mut ones, mut threes) = (0, 1);
+    for i in 0..size {
+        let (last, _) = *ways.back().unwrap();
+        let one = ways.iter().map(|&(_, w)| w).sum::<u64>() < u64::MAX / 4;
+        let gap = if i == 0 || (one && rng.gen_bool(0.7)) {
+            ones += 1;
+            1
+        } else {
+            threes += 1;
+            3
+        };
+        let joltage = last + gap;
+        let reach: u64 = ways
+            .iter()
+            .filter(|&&(j, _)| joltage - j <= 3)
+            .map(|&(_, w)| w)
+            .sum();
+        ways.push_back((joltage, reach));
+        if ways.len() > 3 {
+            ways.pop_front();
+        }
+        adapters.push(joltage);
+    }
+    let arrangements = ways.back().unwrap().1;
+    adapters.shuffle(rng);
+    Generated::new(lines(adapters), mode, || both(ones * threes, arrangements))
+}
+
+/// Big rooms of seats can flip between full and empty forever, so there's plenty of floor to break
+/// them up. Layouts which never settle even so are thrown away whatever the mode, since the solver
+/// would go round forever.
+fn day11(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    loop {
+        let seats: Vec<Vec<u8>> = (0..size)
+            .map(|_| {
+                (0..size)
+                    .map(|_| if rng.gen_bool(0.4) { b'.' } else { b'L' })
+                    .collect()
+            })
+            .collect();
+        if let (Some(near), Some(far)) = (settle(&seats, false, 4), settle(&seats, true, 5)) {
+            let input = lines(seats.iter().map(|row| String::from_utf8_lossy(row)));
+            return Generated::new(input, mode, || both(near, far));
+        }
+    }
+}
+
+/// How many seats end up taken, when people look at the nearest seat in each direction if
+/// `look_far`, or only at the adjacent places otherwise; `None` if they never settle.
+fn settle(seats: &[Vec<u8>], look_far: bool, threshold: usize) -> Option<usize> {
+    let mut seats = seats.to_vec();
+    let mut before = Vec::new();
+    let (height, width) = (seats.len() as isize, seats[0].len() as isize);
+    loop {
+        let occupied = |seats: &[Vec<u8>], x: isize, y: isize| {
+            let mut count = 0;
+            for (dx, dy) in (-1..=1).flat_map(|dx| (-1..=1).map(move |dy| (dx, dy))) {
+                if (dx, dy) == (0, 0) {
+                    continue;
+                }
+                let (mut x, mut y) = (x + dx, y + dy);
+                while (0..width).contains(&x) && (0..height).contains(&y) {
+                    match seats[y as usize][x as usize] {
+                        b'#' => count += 1,
+                        b'L' => {}
+                        _ if look_far => {
+                            x += dx;
+                            y += dy;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                    break;
+                }
+            }
+            count
+        };
+        let mut next = seats.clone();
+        for y in 0..height {
+            for x in 0..width {
+                let seat = &mut next[y as usize][x as usize];
+                match (*seat, occupied(&seats, x, y)) {
+                    (b'L', 0) => *seat = b'#',
+                    (b'#', n) if n >= threshold => *seat = b'L',
+                    _ => {}
+                }
+            }
+        }
+        if next == seats {
+            return Some(seats.iter().flatten().filter(|&&s| s == b'#').count());
+        }
+        if next == before {
+            return None;
+        }
+        before = std::mem::replace(&mut seats, next);
+    }
+}
+
+fn day12(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let actions: Vec<(char, i64)> = (0..size)
+        .map(|_| match *b"NSEWLRF".choose(rng).unwrap() as char {
+            turn @ ('L' | 'R') => (turn, 90 * rng.gen_range(1..=3)),
+            action => (action, rng.gen_range(1..=100)),
+        })
+        .collect();
+    let input = lines(actions.iter().map(|(a, n)| format!("{}{}", a, n)));
+    Generated::new(input, mode, || {
+        // Either the ship's heading or the waypoint is the thing which moves and turns.
+        let navigate = |mut pointer: (i64, i64), waypoint: bool| {
+            let mut ship = (0, 0);
+            for &(action, n) in &actions {
+                let moving = if waypoint { &mut pointer } else { &mut ship };
+                match action {
+                    'N' => moving.1 += n,
+                    'S' => moving.1 -= n,
+                    'E' => moving.0 += n,
+                    'W' => moving.0 -= n,
+                    'L' | 'R' => {
+                        let quarters = if action == 'L' { n / 90 } else { 4 - n / 90 };
+                        for _ in 0..quarters {
+                            pointer = (-pointer.1, pointer.0);
+                        }
+                    }
+                    _ => ship = (ship.0 + n * pointer.0, ship.1 + n * pointer.1),
+                }
+            }
+            ship.0.abs() + ship.1.abs()
+        };
+        both(navigate((1, 0), false), navigate((10, 1), true))
+    })
+}
+
+/// Buses with prime ids, so that there's always a time at which they leave one after another.
+fn day13(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let primes: Vec<u64> = (7..500u64)
+        .filter(|&n| (2..n).take_while(|d| d * d <= n).all(|d| n % d != 0))
+        .collect();
+    let buses: Vec<u64> = primes.choose_multiple(rng, size).copied().collect();
+    let mut ids = Vec::new();
+    let mut offsets = Vec::new();
+    for (i, &bus) in buses.iter().enumerate() {
+        if i > 0 {
+            for _ in 0..rng.gen_range(0..=8) {
+                ids.push("x".to_owned());
+            }
+        }
+        offsets.push(ids.len() as u128);
+        ids.push(bus.to_string());
+    }
+    // The earliest bus must be the only one to leave then.
+    let (depart_after, earliest) = loop {
+        let depart_after: u64 = rng.gen_range(1..1_000_000);
+        let mut waits: Vec<(u64, u64)> = buses
+            .iter()
+            .map(|&b| ((b - depart_after % b) % b, b))
+            .collect();
+        waits.sort_unstable();
+        if waits.len() == 1 || waits[0].0 != waits[1].0 {
+            break (depart_after, waits[0]);
+        }
+    };
+    let input = format!("{}\n{}\n", depart_after, ids.join(","));
+    Generated::new(input, mode, || {
+        let (mut time, mut step) = (0u128, 1u128);
+        for (&bus, &offset) in buses.iter().zip(&offsets) {
+            while (time + offset) % bus as u128 != 0 {
+                time += step;
+            }
+            step *= bus as u128;
+        }
+        both(earliest.0 * earliest.1, time)
+    })
+}
+
+fn day14(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    const BITS: usize = 36;
+    // Each mask is followed by the writes it applies to.
+    let mut program: Vec<(String, Vec<(u64, u64)>)> = Vec::new();
+    let mut writes = 0;
+    while writes < size {
+        let mut mask: Vec<char> = (0..BITS)
+            .map(|_| if rng.gen() { '1' } else { '0' })
+            .collect();
+        let floating = rng.gen_range(2..=8);
+        for i in index::sample(rng, BITS, floating) {
+            mask[i] = 'X';
+        }
+        let n = rng.gen_range(1..=6).min(size - writes);
+        let block = (0..n)
+            .map(|_| (rng.gen_range(0..1 << 16), rng.gen_range(0..1 << BITS)))
+            .collect();
+        program.push((mask.into_iter().collect(), block));
+        writes += n;
+    }
+    let mut input = String::new();
+    for (mask, block) in &program {
+        writeln!(input, "mask = {}", mask).unwrap();
+        for (address, value) in block {
+            writeln!(input, "mem[{}] = {}", address, value).unwrap();
+        }
+    }
+    Generated::new(input, mode, || {
+        let (mut v1, mut v2) = (HashMap::new(), HashMap::new());
+        for (mask, block) in &program {
+            let bits = |c| {
+                mask.chars()
+                    .rev()
+                    .enumerate()
+                    .filter(move |&(_, m)| m == c)
+                    .map(|(i, _)| 1u64 << i)
+            };
+            let ones: u64 = bits('1').sum();
+            let zeros: u64 = bits('0').sum();
+            let floating: Vec<u64> = bits('X').collect();
+            for &(address, value) in block {
+                v1.insert(address, (value | ones) & !zeros);
+                for choice in 0..1u64 << floating.len() {
+                    let mut address = address | ones;
+                    for (i, &bit) in floating.iter().enumerate() {
+                        address = match choice & 1 << i {
+                            0 => address & !bit,
+                            _ => address | bit,
+                        };
+                    }
+                    v2.insert(address, value);
+                }
+            }
+        }
+        both(v1.values().sum::<u64>(), v2.values().sum::<u64>())
+    })
+}
+
+/// Known answers only cover part 1, which is 2020 turns; part 2 is thirty million.
+fn day15(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let start: Vec<usize> = index::sample(rng, size * 3, size).into_iter().collect();
+    let input = format!("{}\n", itertools::join(&start, ","));
+    Generated::new(input, mode, || {
+        let mut spoken_at = HashMap::new();
+        let mut last = start[0];
+        for turn in 1..2020 {
+            let next = match start.get(turn) {
+                Some(&n) => n,
+                None => spoken_at.get(&last).map_or(0, |&t| turn - t),
+            };
+            spoken_at.insert(last, turn);
+            last = next;
+        }
+        vec![(Part::One, last.to_string())]
+    })
+}
+
+const FIELDS: [&str; 20] = [
+    "departure location",
+    "departure station",
+    "departure platform",
+    "departure track",
+    "departure date",
+    "departure time",
+    "arrival location",
+    "arrival station",
+    "arrival platform",
+    "arrival track",
+    "class",
+    "duration",
+    "price",
+    "route",
+    "row",
+    "seat",
+    "train",
+    "type",
+    "wagon",
+    "zone",
+];
+
+/// The fields' ranges are nested, less a gap in each, like the real thing, so that there's only
+/// one way to match fields to columns. Invalid values are beyond every range.
+fn day16(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let band = 900 / size;
+    let departures = (size / 3).clamp(1, 6);
+    let mut names: Vec<&str> = FIELDS[..departures].to_vec();
+    names.extend(FIELDS[6..].choose_multiple(rng, size - departures));
+
+    // Field k accepts 1 to its top, bar a gap, and each column has a value above the top of the
+    // field below it.
+    let tops: Vec<usize> = (1..=size).map(|k| k * band).collect();
+    let gaps: Vec<usize> = tops.iter().map(|&top| rng.gen_range(2..top - 1)).collect();
+    let mut columns: Vec<usize> = (0..size).collect();
+    columns.shuffle(rng);
+    let value = |rng: &mut Prng, field: usize, high: bool| loop {
+        let low = if high { tops[field] - band + 1 } else { 1 };
+        let v = rng.gen_range(low..=tops[field]);
+        if !gaps.contains(&v) {
+            break v;
+        }
+    };
+    let ticket = |rng: &mut Prng, high: Option<usize>| {
+        let mut values = vec![0; size];
+        for field in 0..size {
+            values[columns[field]] = value(rng, field, high == Some(field));
+        }
+        values
+    };
+
+    let mine = ticket(rng, None);
+    let mut nearby: Vec<Vec<usize>> = (0..size).map(|field| ticket(rng, Some(field))).collect();
+    let mut error_rate = 0;
+    for _ in 0..size * 8 {
+        let mut values = ticket(rng, None);
+        if rng.gen_bool(0.25) {
+            let bad = rng.gen_range(901..1000);
+            values[rng.gen_range(0..size)] = bad;
+            error_rate += bad;
+        }
+        nearby.push(values);
+    }
+    nearby.shuffle(rng);
+
+    let mut rules: Vec<String> = (0..size)
+        .map(|k| {
+            format!(
+                "{}: 1-{} or {}-{}",
+                names[k],
+                gaps[k] - 1,
+                gaps[k] + 1,
+                tops[k]
+            )
+        })
+        .collect();
+    rules.shuffle(rng);
+    let join = |values: &[usize]| itertools::join(values, ",");
+    let input = format!(
+        "{}\nyour ticket:\n{}\n\nnearby tickets:\n{}",
+        lines(rules),
+        join(&mine),
+        lines(nearby.iter().map(|t| join(t)))
+    );
+    Generated::new(input, mode, || {
+        let departing: usize = (0..departures).map(|k| mine[columns[k]]).product();
+        both(error_rate, departing)
+    })
+}
+
+fn day17(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let cells: Vec<Vec<bool>> = (0..size)
+        .map(|_| (0..size).map(|_| rng.gen_bool(0.4)).collect())
+        .collect();
+    let input = lines(cells.iter().map(|row| {
+        row.iter()
+            .map(|&c| if c { '#' } else { '.' })
+            .collect::<String>()
+    }));
+    Generated::new(input, mode, || {
+        let active: HashSet<[i32; 4]> = cells
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.iter()
+                    .enumerate()
+                    .filter(|(_, &c)| c)
+                    .map(move |(x, _)| [x as i32, y as i32, 0, 0])
+            })
+            .collect();
+        both(conway(&active, 3), conway(&active, 4))
+    })
+}
+
+/// How many cubes are active after six cycles in `dims` dimensions.
+fn conway(active: &HashSet<[i32; 4]>, dims: usize) -> usize {
+    let offsets: Vec<[i32; 4]> = (0..3i32.pow(dims as u32))
+        .map(|mut n| {
+            let mut offset = [0; 4];
+            for o in offset.iter_mut().take(dims) {
+                *o = n % 3 - 1;
+                n /= 3;
+            }
+            offset
+        })
+        .filter(|o| o != &[0; 4])
+        .collect();
+    let mut active = active.clone();
+    for _ in 0..6 {
+        let mut neighbours: HashMap<[i32; 4], usize> = HashMap::new();
+        for cube in &active {
+            for o in &offsets {
+                let n = [
+                    cube[0] + o[0],
+                    cube[1] + o[1],
+                    cube[2] + o[2],
+                    cube[3] + o[3],
+                ];
+                *neighbours.entry(n).or_insert(0) += 1;
+            }
+        }
+        active = neighbours
+            .into_iter()
+            .filter(|(cube, n)| *n == 3 || (*n == 2 && active.contains(cube)))
+            .map(|(cube, _)| cube)
+            .collect();
+    }
+    active.len()
+}
+
+/// Operands joined by operators, as inside a pair of brackets.
+struct Sequence {
+    operands: Vec<Operand>,
+    operators: Vec<char>,
+}
+
+enum Operand {
+    Number(u128),
+    Group(Sequence),
+}
+
+impl Sequence {
+    fn random(rng: &mut Prng, depth: usize) -> Self {
+        let operands: Vec<_> = (0..rng.gen_range(2..=5))
+            .map(|_| match depth < 3 && rng.gen_bool(0.25) {
+                true => Operand::Group(Self::random(rng, depth + 1)),
+                false => Operand::Number(rng.gen_range(1..=9)),
+            })
+            .collect();
+        let operators = (1..operands.len())
+            .map(|_| if rng.gen() { '+' } else { '*' })
+            .collect();
+        Self {
+            operands,
+            operators,
+        }
+    }
+
+    fn write(&self, out: &mut String) {
+        for (i, operand) in self.operands.iter().enumerate() {
+            if i > 0 {
+                write!(out, " {} ", self.operators[i - 1]).unwrap();
+            }
+            match operand {
+                Operand::Number(n) => write!(out, "{}", n).unwrap(),
+                Operand::Group(inner) => {
+                    out.push('(');
+                    inner.write(out);
+                    out.push(')');
+                }
+            }
+        }
+    }
+
+    /// The value with the operators done left to right, or with addition first.
+    fn evaluate(&self, addition_first: bool) -> u128 {
+        let values = self.operands.iter().map(|operand| match operand {
+            Operand::Number(n) => *n,
+            Operand::Group(inner) => inner.evaluate(addition_first),
+        });
+        let mut terms = vec![];
+        let mut total = 0;
+        for (i, value) in values.enumerate() {
+            match i.checked_sub(1).map(|o| self.operators[o]) {
+                None => total = value,
+                Some('+') => total += value,
+                Some(_) if addition_first => terms.push(std::mem::replace(&mut total, value)),
+                Some(_) => total *= value,
+            }
+        }
+        terms.into_iter().product::<u128>() * total
+    }
+}
+
+fn day18(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let (mut left_to_right, mut addition_first) = (0, 0);
+    let mut input = String::new();
+    let mut written = 0;
+    while written < size {
+        let expression = Sequence::random(rng, 0);
+        let (a, b) = (expression.evaluate(false), expression.evaluate(true));
+        // Keep the sums well inside a u64.
+        if a.max(b) >= 1 << 40 {
+            continue;
+        }
+        left_to_right += a;
+        addition_first += b;
+        expression.write(&mut input);
+        input.push('\n');
+        written += 1;
+    }
+    Generated::new(input, mode, || both(left_to_right, addition_first))
+}
+
+/// Which of two labels each string of some length has, splitting by one letter at a time.
+enum Trie {
+    Same(bool),
+    Split(Box<Trie>, Box<Trie>),
+}
+
+impl Trie {
+    fn random(rng: &mut Prng, depth: usize) -> Self {
+        if depth == CHUNK || (depth >= 2 && rng.gen_bool(0.3)) {
+            Trie::Same(rng.gen())
+        } else {
+            Trie::Split(
+                Box::new(Self::random(rng, depth + 1)),
+                Box::new(Self::random(rng, depth + 1)),
+            )
+        }
+    }
+
+    fn has(&self, label: bool) -> bool {
+        match self {
+            Trie::Same(l) => *l == label,
+            Trie::Split(a, b) => a.has(label) || b.has(label),
+        }
+    }
+
+    fn label(&self, s: &[u8]) -> bool {
+        match self {
+            Trie::Same(l) => *l,
+            Trie::Split(a, b) => match s[0] {
+                b'a' => a.label(&s[1..]),
+                _ => b.label(&s[1..]),
+            },
+        }
+    }
+
+    /// A random string with the label, finishing one `depth` letters in.
+    fn sample(&self, rng: &mut Prng, label: bool, depth: usize, out: &mut String) {
+        match self {
+            Trie::Same(_) => {
+                for _ in depth..CHUNK {
+                    out.push(if rng.gen() { 'a' } else { 'b' });
+                }
+            }
+            Trie::Split(a, b) => {
+                let take_a = match (a.has(label), b.has(label)) {
+                    (true, true) => rng.gen(),
+                    (has_a, _) => has_a,
+                };
+                out.push(if take_a { 'a' } else { 'b' });
+                let next = if take_a { a } else { b };
+                next.sample(rng, label, depth + 1, out);
+            }
+        }
+    }
+}
+
+/// Rules 42 and 31 match strings of this length, which the solver relies on.
+const CHUNK: usize = 8;
+
+/// Writes the rules for a trie's languages, numbering them at random.
+struct Rules {
+    numbers: Vec<usize>,
+    lines: Vec<String>,
+    letters: [usize; 2],
+    /// `any[k]` matches any string of length k.
+    any: Vec<usize>,
+}
+
+impl Rules {
+    fn add(&mut self, number: Option<usize>, body: String) -> usize {
+        let number = number.unwrap_or_else(|| self.numbers.pop().unwrap());
+        self.lines.push(format!("{}: {}", number, body));
+        number
+    }
+
+    /// The choices a rule matching strings of `node` with `label` has, if it matches any.
+    fn choices(&mut self, node: &Trie, label: bool, depth: usize) -> Option<Vec<Vec<usize>>> {
+        match node {
+            Trie::Same(l) if *l != label => None,
+            Trie::Same(_) if depth == CHUNK => Some(vec![vec![]]),
+            Trie::Same(_) => Some(vec![vec![self.any[CHUNK - depth]]]),
+            Trie::Split(a, b) => {
+                let mut choices = Vec::new();
+                let letters = self.letters;
+                for (&letter, child) in letters.iter().zip(&[a, b]) {
+                    if let Some(inner) = self.choices(child, label, depth + 1) {
+                        let mut choice = vec![letter];
+                        choice.extend(self.refer(inner));
+                        choices.push(choice);
+                    }
+                }
+                Some(choices).filter(|c| !c.is_empty())
+            }
+        }
+    }
+
+    /// A sequence of rules which matches any of the choices.
+    fn refer(&mut self, mut choices: Vec<Vec<usize>>) -> Vec<usize> {
+        if choices.len() == 1 {
+            choices.remove(0)
+        } else {
+            vec![self.add(None, Self::body(&choices))]
+        }
+    }
+
+    fn body(choices: &[Vec<usize>]) -> String {
+        let choices: Vec<String> = choices.iter().map(|c| itertools::join(c, " ")).collect();
+        choices.join(" | ")
+    }
+}
+
+/// Rule 42 matches chunks with one label and rule 31 those with the other. Rule 0 is the usual
+/// `8 11`; part 2's loops are left for the solver to add.
+fn day19(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    let trie = loop {
+        let trie = Trie::random(rng, 0);
+        if matches!(trie, Trie::Split(..)) && trie.has(true) && trie.has(false) {
+            break trie;
+        }
+    };
+
+    let mut numbers: Vec<usize> = (1..2000).filter(|n| ![8, 11, 31, 42].contains(n)).collect();
+    numbers.shuffle(rng);
+    let mut rules = Rules {
+        numbers,
+        lines: Vec::new(),
+        letters: [0, 0],
+        any: vec![0; CHUNK + 1],
+    };
+    rules.add(Some(0), "8 11".to_owned());
+    rules.add(Some(8), "42".to_owned());
+    rules.add(Some(11), "42 31".to_owned());
+    rules.letters = [
+        rules.add(None, "\"a\"".to_owned()),
+        rules.add(None, "\"b\"".to_owned()),
+    ];
+    let [a, b] = rules.letters;
+    rules.any[1] = rules.add(None, format!("{} | {}", a, b));
+    for k in 2..=CHUNK {
+        let body = format!("{} {}", rules.any[1], rules.any[k - 1]);
+        rules.any[k] = rules.add(None, body);
+    }
+    for &(number, label) in &[(42, true), (31, false)] {
+        let choices = rules.choices(&trie, label, 0).unwrap();
+        rules.add(Some(number), Rules::body(&choices));
+    }
+    let mut rule_lines = rules.lines;
+    rule_lines.shuffle(rng);
+
+    let (mut old, mut new) = (0, 0);
+    let mut messages = Vec::new();
+    for _ in 0..size {
+        let chunks = rng.gen_range(2..=8);
+        let labels: Vec<bool> = match rng.gen() {
+            true => {
+                let first = rng.gen_range(1..chunks);
+                (0..chunks).map(|i| i < first).collect()
+            }
+            false => (0..chunks).map(|_| rng.gen()).collect(),
+        };
+        let mut message = String::new();
+        for &label in &labels {
+            trie.sample(rng, label, 0, &mut message);
+        }
+        let tail = rng.gen_bool(0.1);
+        if tail {
+            for _ in 0..rng.gen_range(1..CHUNK) {
+                message.push(if rng.gen() { 'a' } else { 'b' });
+            }
+        } else {
+            let labels: Vec<bool> = message
+                .as_bytes()
+                .chunks(CHUNK)
+                .map(|c| trie.label(c))
+                .collect();
+            let firsts = labels.iter().take_while(|&&l| l).count();
+            let seconds = labels.len() - firsts;
+            let well_formed = labels[firsts..].iter().all(|&l| !l);
+            old += (labels == [true, true, false]) as usize;
+            new += (well_formed && firsts > seconds && seconds >= 1) as usize;
+        }
+        messages.push(message);
+    }
+    let input = format!("{}\n{}", lines(rule_lines), lines(messages));
+    Generated::new(input, mode, || both(old, new))
+}
+
+fn day20(rng: &mut Prng, size: usize, mode: Mode) -> Generated {
+    jigsaw(rng, size, size, 10, mode)
+}
+
+/// Cuts a random picture into `width` by `height` tiles, each `tile` cells square, which overlap
+/// at their edges. In `Mode::Known` every edge is different, even flipped, so that the tiles only
+/// fit together one way.
+pub(crate) fn jigsaw(
+    rng: &mut Prng,
+    width: usize,
+    height: usize,
+    tile: usize,
+    mode: Mode,
+) -> Generated {
+    let step = tile - 1;
+    let mut picture: Vec<Vec<bool>> = (0..step * height + 1)
+        .map(|_| (0..step * width + 1).map(|_| rng.gen()).collect())
+        .collect();
+
+    // Each edge is a tile's width along a row or a column; only the cells between its ends are
+    // its own.
+    let rows = (0..=height).flat_map(|line| {
+        (0..width).map(move |n| (0..tile).map(|k| (step * line, step * n + k)).collect())
+    });
+    let columns = (0..=width).flat_map(|line| {
+        (0..height).map(move |n| (0..tile).map(|k| (step * n + k, step * line)).collect())
+    });
+    let edges: Vec<Vec<(usize, usize)>> = rows.chain(columns).collect();
+    if mode == Mode::Known {
+        while let Some(edge) = clashing_edge(&picture, &edges) {
+            for &(y, x) in &edge[1..step] {
+                picture[y][x] = rng.gen();
+            }
+        }
+    }
+
+    let ids: Vec<usize> = index::sample(rng, 9000, width * height)
+        .into_iter()
+        .map(|i| i + 1000)
+        .collect();
+    let mut tiles: Vec<String> = Vec::new();
+    for (n, &id) in ids.iter().enumerate() {
+        let (y0, x0) = (step * (n / width), step * (n % width));
+        let mut grid = Grid::from_fn(tile, tile, |x, y| picture[y0 + y][x0 + x]);
+        for _ in 0..rng.gen_range(0..4) {
+            grid = grid.rotate_cw();
+        }
+        if rng.gen() {
+            grid = grid.flip_horizontal();
+        }
+        let mut text = format!("Tile {}:\n", id);
+        for y in 0..grid.height() {
+            text.extend(grid.row(y).map(|&c| if c { '#' } else { '.' }));
+            text.push('\n');
+        }
+        tiles.push(text);
+    }
+    tiles.shuffle(rng);
+    let input = tiles.join("\n");
+    Generated::new(input, mode, || {
+        let corners = [0, width - 1, width * (height - 1), width * height - 1];
+        vec![(
+            Part::One,
+            corners
+                .iter()
+                .unique()
+                .map(|&n| ids[n])
+                .product::<usize>()
+                .to_string(),
+        )]
+    })
+}
+
+/// An edge which reads the same both ways, or the same as another edge either way.
+fn clashing_edge<'a>(
+    picture: &[Vec<bool>],
+    edges: &'a [Vec<(usize, usize)>],
+) -> Option<&'a Vec<(usize, usize)>> {
+    let mut seen = HashSet::new();
+    edges.iter().find(|edge| {
+        let cells: Vec<bool> = edge.iter().map(|&(y, x)| picture[y][x]).collect();
+        let flipped: Vec<bool> = cells.iter().rev().copied().collect();
+        cells == flipped || !seen.insert(cells.min(flipped))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn known_answers() {
+        for generator in &GENERATORS {
+            let day = days::find(generator.day).unwrap();
+            let size = (*generator.sizes.start()).max(generator.default_size / 8);
+            for seed in 0..3 {
+                let generated = generator.generate(seed, size, Mode::Known);
+                let parsed = day
+                    .parse(&generated.input)
+                    .unwrap_or_else(|e| panic!("day {} seed {}: {}", generator.day, seed, e));
+                assert!(!generated.expected.is_empty());
+                for (part, expected) in &generated.expected {
+                    assert_eq!(
+                        &day.solve(*part, &parsed).to_string(),
+                        expected,
+                        "day {} part {} seed {}\n{}",
+                        generator.day,
+                        part,
+                        seed,
+                        generated.input
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn random_inputs() {
+        for generator in &GENERATORS {
+            let day = days::find(generator.day).unwrap();
+            for &size in &[*generator.sizes.start(), generator.default_size] {
+                let generated = generator.generate(7, size, Mode::Random);
+                assert!(generated.expected.is_empty());
+                if let Err(e) = day.parse(&generated.input) {
+                    panic!("day {} size {}: {}", generator.day, size, e);
+                }
+                assert_eq!(generator.generate(7, size, Mode::Random), generated);
+            }
+            assert!(generator.sizes.contains(&generator.default_size));
+        }
+        assert_ne!(
+            find(7).unwrap().generate(1, 50, Mode::Random),
+            find(7).unwrap().generate(2, 50, Mode::Random)
+        );
+    }
+
+    #[test]
+    fn expected_files() {
+        let generated = find(5).unwrap().generate(3, 10, Mode::Known);
+        let file = generated.expected_file();
+        assert!(file.starts_with("part1: "));
+        assert_eq!(file.lines().count(), 2);
+    }
+}
@@ -0,0 +1,373 @@
+//! A rectangular grid of cells, as found in most puzzles. Positions are `(x, y)` with `x` counting
+//! columns left to right and `y` counting rows top to bottom.
+
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::error::ParseError;
+
+/// Up, right, down and left, in that order.
+pub const ORTHOGONAL: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// The orthogonal directions and the diagonals.
+pub const ALL_DIRECTIONS: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// What happens when a signed position falls off the edge of the grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Wrap {
+    /// Nothing's there.
+    None,
+    /// The grid repeats forever to the left and right.
+    Horizontal,
+    /// The grid repeats forever upwards and downwards.
+    Vertical,
+    /// The grid repeats in every direction.
+    Torus,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+    wrap: Wrap,
+}
+
+impl<T> Grid<T> {
+    /// A grid from its cells in row-major order, or `None` if there are the wrong number of them.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Option<Self> {
+        if cells.len() == width * height {
+            Some(Self {
+                width,
+                height,
+                cells,
+                wrap: Wrap::None,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+        Self {
+            width,
+            height,
+            cells,
+            wrap: Wrap::None,
+        }
+    }
+
+    /// Read a grid with one character per cell. Every row must be the same width, and `cell` must
+    /// accept every character; `expected` describes the ones it accepts. Errors are positioned as
+    /// if the rows were the lines of a file.
+    pub fn from_rows<'a>(
+        rows: impl IntoIterator<Item = &'a str>,
+        expected: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut height = 0;
+        for (y, row) in rows.into_iter().enumerate() {
+            let before = cells.len();
+            for (x, c) in row.chars().enumerate() {
+                if width == Some(x) {
+                    let rest: String = row.chars().skip(x).collect();
+                    return Err(ParseError::new(y + 1, x + 1, "the end of the row", rest));
+                }
+                let value = cell(c).ok_or_else(|| ParseError::new(y + 1, x + 1, expected, c))?;
+                cells.push(value);
+            }
+            let row_width = cells.len() - before;
+            match width {
+                None => width = Some(row_width),
+                Some(w) if w != row_width => {
+                    let expected = format!("a row {} wide", w);
+                    return Err(ParseError::new(y + 1, row_width + 1, expected, ""));
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok(Self {
+                width,
+                height,
+                cells,
+                wrap: Wrap::None,
+            }),
+            _ => Err(ParseError::new(1, 1, "a grid", "")),
+        }
+    }
+
+    /// Like `from_rows`, taking each line of `input` as a row.
+    pub fn parse(
+        input: &str,
+        expected: &str,
+        cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseError> {
+        Self::from_rows(input.lines(), expected, cell)
+    }
+
+    pub fn with_wrap(self, wrap: Wrap) -> Self {
+        Self { wrap, ..self }
+    }
+
+    pub fn wrap(&self) -> Wrap {
+        self.wrap
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Where a signed position lands, taking wrapping into account.
+    fn locate(&self, x: isize, y: isize) -> Option<usize> {
+        let wrap = |v: isize, size: usize, wraps: bool| -> Option<usize> {
+            if wraps {
+                Some(v.rem_euclid(size as isize) as usize)
+            } else if 0 <= v && v < size as isize {
+                Some(v as usize)
+            } else {
+                None
+            }
+        };
+        let x = wrap(
+            x,
+            self.width,
+            self.wrap == Wrap::Horizontal || self.wrap == Wrap::Torus,
+        )?;
+        let y = wrap(
+            y,
+            self.height,
+            self.wrap == Wrap::Vertical || self.wrap == Wrap::Torus,
+        )?;
+        Some(y * self.width + x)
+    }
+
+    pub fn get(&self, x: isize, y: isize) -> Option<&T> {
+        self.locate(x, y).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, x: isize, y: isize) -> Option<&mut T> {
+        self.locate(x, y).map(move |i| &mut self.cells[i])
+    }
+
+    /// Every cell, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// Every position, in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn row(&self, y: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn column(&self, x: usize) -> impl DoubleEndedIterator<Item = &T> {
+        self.cells[x..].iter().step_by(self.width)
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+            wrap: self.wrap,
+        }
+    }
+
+    /// The cells one step away from `(x, y)` in each of the given directions, along with the
+    /// direction they're in. Directions leading off the grid are skipped.
+    pub fn neighbours<'a>(
+        &'a self,
+        x: isize,
+        y: isize,
+        directions: &'a [(isize, isize)],
+    ) -> impl Iterator<Item = ((isize, isize), &'a T)> + 'a {
+        directions
+            .iter()
+            .filter_map(move |&(dx, dy)| self.get(x + dx, y + dy).map(|t| ((dx, dy), t)))
+    }
+
+    pub fn neighbours4(&self, x: isize, y: isize) -> impl Iterator<Item = &T> {
+        self.neighbours(x, y, &ORTHOGONAL).map(|(_, t)| t)
+    }
+
+    pub fn neighbours8(&self, x: isize, y: isize) -> impl Iterator<Item = &T> {
+        self.neighbours(x, y, &ALL_DIRECTIONS).map(|(_, t)| t)
+    }
+
+    /// The cells seen looking from `(x, y)` in the direction `(dx, dy)`, nearest first, not
+    /// including `(x, y)` itself. It stops at the edge of the grid, so on a wrapping grid it may
+    /// never stop.
+    pub fn ray(&self, x: isize, y: isize, (dx, dy): (isize, isize)) -> impl Iterator<Item = &T> {
+        (1..).map_while(move |k| self.get(x + k * dx, y + k * dy))
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn filled(width: usize, height: usize, value: T) -> Self {
+        Self::from_fn(width, height, |_, _| value.clone())
+    }
+
+    /// A quarter turn clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let h = self.height;
+        Self::from_fn(self.height, self.width, |x, y| self[(y, h - 1 - x)].clone())
+            .with_wrap(self.wrap)
+    }
+
+    /// Turn upside down: the top row becomes the bottom row.
+    pub fn flip_vertical(&self) -> Self {
+        let h = self.height;
+        Self::from_fn(self.width, self.height, |x, y| self[(x, h - 1 - y)].clone())
+            .with_wrap(self.wrap)
+    }
+
+    /// Mirror left to right.
+    pub fn flip_horizontal(&self) -> Self {
+        let w = self.width;
+        Self::from_fn(self.width, self.height, |x, y| self[(w - 1 - x, y)].clone())
+            .with_wrap(self.wrap)
+    }
+
+    /// Reflect in the leading diagonal, swapping rows and columns.
+    pub fn transpose(&self) -> Self {
+        Self::from_fn(self.height, self.width, |x, y| self[(y, x)].clone()).with_wrap(self.wrap)
+    }
+}
+
+/// Unsigned indexing ignores wrapping and panics outside the grid.
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the grid",
+            x,
+            y
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        assert!(
+            x < self.width && y < self.height,
+            "({}, {}) is off the grid",
+            x,
+            y
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// One line per row, with no trailing newline.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                f.write_str("\n")?;
+            }
+            for cell in self.row(y) {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn letters(input: &str) -> Grid<char> {
+        Grid::parse(input, "anything", Some).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let grid = letters("abc\ndef");
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef");
+        let position = |input, dots_only: bool| {
+            let e = Grid::parse(input, ".", |c| Some(c).filter(|&c| c == '.' || !dots_only))
+                .unwrap_err();
+            (e.line, e.column, e.found)
+        };
+        assert_eq!(position("ab\nc", false), (2, 2, "".to_owned()));
+        assert_eq!(position("ab\ncde", false), (2, 3, "e".to_owned()));
+        assert_eq!(position("", false), (1, 1, "".to_owned()));
+        assert_eq!(position("..\n.x", true), (2, 2, "x".to_owned()));
+    }
+
+    #[test]
+    fn wrapping() {
+        let grid = letters("abc\ndef");
+        assert_eq!(grid.get(-1, 0), None);
+        assert_eq!(grid.get(3, 1), None);
+        let grid = grid.with_wrap(Wrap::Horizontal);
+        assert_eq!(grid.get(-1, 0), Some(&'c'));
+        assert_eq!(grid.get(7, 1), Some(&'e'));
+        assert_eq!(grid.get(0, 2), None);
+        let grid = grid.with_wrap(Wrap::Torus);
+        assert_eq!(grid.get(-4, -1), Some(&'f'));
+    }
+
+    #[test]
+    fn neighbours_and_rays() {
+        let grid = letters("abc\ndef\nghi");
+        assert_eq!(grid.neighbours4(0, 0).collect::<String>(), "bd");
+        assert_eq!(grid.neighbours8(1, 1).collect::<String>(), "abcdfghi");
+        assert_eq!(grid.neighbours8(2, 2).count(), 3);
+        assert_eq!(grid.ray(0, 0, (1, 1)).collect::<String>(), "ei");
+        assert_eq!(grid.ray(2, 1, (-1, 0)).collect::<String>(), "ed");
+        assert_eq!(grid.ray(0, 0, (0, -1)).count(), 0);
+    }
+
+    #[test]
+    fn rotations_and_reflections() {
+        let grid = letters("abc\ndef");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        let turned = (0..4).fold(grid.clone(), |g, _| g.rotate_cw());
+        assert_eq!(turned, grid);
+        assert_eq!(grid.rotate_cw().flip_horizontal(), grid.transpose());
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = letters("abc\ndef");
+        assert_eq!(grid.row(1).rev().collect::<String>(), "fed");
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.positions().nth(4), Some((1, 1)));
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
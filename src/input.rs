@@ -0,0 +1,232 @@
+use std::env;
+use std::fs;
+use std::io::{self, Read};
+use std::iter::FromIterator;
+use std::path::PathBuf;
+
+use crate::error::{Error, ParseError, Result};
+
+/// Environment variable which overrides where a day's input is read from. It takes the same
+/// values as `InputSource::from_arg`; any `{day}` in it is replaced by the day number.
+pub const INPUT_VAR: &str = "AOC_INPUT";
+
+/// Where to read a puzzle's input from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    Path(PathBuf),
+    Stdin,
+    Text(String),
+}
+
+impl InputSource {
+    /// Interpret a command line argument: `-` means stdin, anything else is a path.
+    pub fn from_arg(arg: &str) -> Self {
+        match arg {
+            "-" => InputSource::Stdin,
+            path => InputSource::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// The input for the given day: `$AOC_INPUT` if that's set, otherwise `./input/dayN.txt`.
+    pub fn for_day(day: usize) -> Self {
+        match env::var(INPUT_VAR) {
+            Ok(value) => Self::from_arg(&value.replace("{day}", &day.to_string())),
+            Err(_) => InputSource::Path(Self::default_path(day)),
+        }
+    }
+
+    pub fn default_path(day: usize) -> PathBuf {
+        let mut path = env::current_dir().unwrap_or_default();
+        path.push("input");
+        path.push(format!("day{}.txt", day));
+        path
+    }
+
+    /// The path to mention when something goes wrong; stdin is reported as `-`.
+    fn path(&self) -> PathBuf {
+        match self {
+            InputSource::Path(path) => path.clone(),
+            InputSource::Stdin => PathBuf::from("-"),
+            InputSource::Text(_) => PathBuf::from("<text>"),
+        }
+    }
+
+    pub fn try_content(&self) -> Result<String> {
+        let bytes = match self {
+            InputSource::Text(text) => return Ok(text.clone()),
+            InputSource::Path(path) => fs::read(path),
+            InputSource::Stdin => {
+                let mut bytes = Vec::new();
+                io::stdin().read_to_end(&mut bytes).map(|_| bytes)
+            }
+        };
+        let bytes = bytes.map_err(|source| match source.kind() {
+            io::ErrorKind::NotFound => Error::MissingInput { path: self.path() },
+            _ => Error::Io {
+                path: self.path(),
+                source,
+            },
+        })?;
+        String::from_utf8(bytes).map_err(|e| Error::Encoding {
+            path: self.path(),
+            valid_up_to: e.utf8_error().valid_up_to(),
+        })
+    }
+
+    pub fn try_lines(&self) -> Result<impl Iterator<Item = String>> {
+        let content = self.try_content()?;
+        let lines: Vec<_> = content.lines().map(String::from).collect();
+        Ok(lines.into_iter())
+    }
+
+    /// The input's blank-line-separated records; see `records`.
+    pub fn try_records(&self) -> Result<impl Iterator<Item = Vec<String>>> {
+        let content = self.try_content()?;
+        let records: Vec<Vec<_>> = records(&content)
+            .map(|r| r.into_iter().map(String::from).collect())
+            .collect();
+        Ok(records.into_iter())
+    }
+
+    pub fn content(&self) -> String {
+        self.try_content().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = String> {
+        self.try_lines().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn records(&self) -> impl Iterator<Item = Vec<String>> {
+        self.try_records().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+/// Groups of lines separated by blank lines, as used by puzzles with multi-line entries. Line
+/// endings may be `\n` or `\r\n`, a line of nothing but whitespace counts as blank, and runs of
+/// blank lines (including any at the start or end) never produce empty records.
+pub fn records(text: &str) -> Records<'_> {
+    Records {
+        lines: text.lines(),
+    }
+}
+
+/// Parse every line of `input` with `parse`, which reports errors relative to the line it was
+/// given; they're moved to where that line sits in the whole input.
+pub fn parse_lines<T, C: FromIterator<T>>(
+    input: &str,
+    mut parse: impl FnMut(&str) -> std::result::Result<T, ParseError>,
+) -> std::result::Result<C, ParseError> {
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|e| e.within(input, line)))
+        .collect()
+}
+
+pub struct Records<'a> {
+    lines: std::str::Lines<'a>,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Vec<&'a str>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let blank = |line: &&str| line.trim().is_empty();
+        let record: Vec<_> = self
+            .lines
+            .by_ref()
+            .skip_while(blank)
+            .take_while(|line| !blank(line))
+            .map(|line| line.trim_end_matches('\r'))
+            .collect();
+        if record.is_empty() {
+            None
+        } else {
+            Some(record)
+        }
+    }
+}
+
+impl From<&str> for InputSource {
+    fn from(text: &str) -> Self {
+        InputSource::Text(text.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn from_arg() {
+        assert_eq!(InputSource::from_arg("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from_arg("elsewhere/day3.txt"),
+            InputSource::Path(PathBuf::from("elsewhere/day3.txt"))
+        );
+    }
+
+    #[test]
+    fn text() {
+        let source = InputSource::from("1721\n979\n366\n");
+        assert_eq!(
+            source.lines().collect::<Vec<_>>(),
+            vec!["1721", "979", "366"]
+        );
+        assert_eq!(source.content(), "1721\n979\n366\n");
+    }
+
+    #[test]
+    fn blank_line_records() {
+        let expected = vec![vec!["a", "b"], vec!["c"], vec!["d", "e"]];
+        for text in &[
+            "a\nb\n\nc\n\nd\ne",
+            "a\nb\n\nc\n\nd\ne\n",
+            "a\r\nb\r\n\r\nc\r\n\r\nd\r\ne\r\n",
+            "\n\na\nb\n\n\n  \nc\n\nd\ne\n\n\n",
+        ] {
+            assert_eq!(records(text).collect::<Vec<_>>(), expected, "{:?}", text);
+        }
+        assert_eq!(records("\n\n").count(), 0);
+        assert_eq!(
+            InputSource::from("x\n\ny\n").records().collect::<Vec<_>>(),
+            vec![vec!["x".to_owned()], vec!["y".to_owned()]]
+        );
+    }
+
+    #[test]
+    fn line_by_line() {
+        let number = |line: &str| {
+            line.parse::<u8>()
+                .map_err(|_| ParseError::at(line, line, "a number"))
+        };
+        assert_eq!(parse_lines("1\n2\n", number), Ok(vec![1, 2]));
+        let e = parse_lines::<_, Vec<_>>("1\n2\nthree\n", number).unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (3, 1, "three"));
+    }
+
+    #[test]
+    fn missing_file() {
+        let path = env::temp_dir().join("aoc-2020-no-such-input.txt");
+        match InputSource::Path(path.clone()).try_content() {
+            Err(Error::MissingInput { path: tried }) => assert_eq!(tried, path),
+            other => panic!("expected MissingInput, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn bad_encoding() {
+        let path = env::temp_dir().join("aoc-2020-latin1-input.txt");
+        fs::write(&path, b"caf\xe9\n").unwrap();
+        match InputSource::Path(path.clone()).try_content() {
+            Err(Error::Encoding {
+                path: tried,
+                valid_up_to,
+            }) => {
+                assert_eq!(tried, path);
+                assert_eq!(valid_up_to, 3);
+            }
+            other => panic!("expected Encoding, got {:?}", other),
+        }
+        fs::remove_file(path).unwrap();
+    }
+}
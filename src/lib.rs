@@ -4,38 +4,74 @@ extern crate lazy_static;
 #[macro_use(scan_fmt)]
 extern crate scan_fmt;
 
-use std::env;
-use std::fs::File;
-use std::io::BufReader;
-use std::io::{BufRead, Read};
+#[macro_use(debug, trace, log_enabled)]
+extern crate log;
 
+pub mod answers;
+pub mod bench;
+pub mod client;
+pub mod days;
+pub mod error;
+pub mod examples;
+pub mod generate;
+pub mod grid;
+pub mod input;
 pub mod lib20;
+pub mod pattern;
+pub mod recording;
+pub mod runner;
+pub mod scan;
+pub mod solution;
+pub mod submit;
+pub mod watch;
 
-// Lots of unwraps here---naughty helpers!
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day16;
+pub mod day17;
+pub mod day18;
+pub mod day19;
+pub mod day2;
+pub mod day20;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
 
-fn problem_name() -> String {
-    env::current_exe()
-        .unwrap()
-        .file_name()
-        .unwrap()
-        .to_str()
-        .unwrap()
-        .to_owned()
+pub use error::{Error, ParseError, Result};
+pub use input::InputSource;
+pub use solution::{Answer, Solution};
+
+pub fn try_problem_lines(day: usize) -> Result<impl Iterator<Item = String>> {
+    InputSource::for_day(day).try_lines()
+}
+
+pub fn try_problem_content(day: usize) -> Result<String> {
+    InputSource::for_day(day).try_content()
 }
 
-fn problem_input() -> BufReader<File> {
-    let mut path = env::current_dir().unwrap();
-    path.push("input");
-    path.push(format!("{}.txt", problem_name()));
-    BufReader::new(File::open(path).unwrap())
+pub fn try_problem_records(day: usize) -> Result<impl Iterator<Item = Vec<String>>> {
+    InputSource::for_day(day).try_records()
+}
+
+// These three panic if the input can't be read---naughty helpers!
+
+pub fn problem_lines(day: usize) -> impl Iterator<Item = String> {
+    InputSource::for_day(day).lines()
 }
 
-pub fn problem_lines() -> impl Iterator<Item = String> {
-    problem_input().lines().map(|r| r.unwrap())
+pub fn problem_content(day: usize) -> String {
+    InputSource::for_day(day).content()
 }
 
-pub fn problem_content() -> String {
-    let mut s = String::new();
-    problem_input().read_to_string(&mut s).unwrap();
-    s
+pub fn problem_records(day: usize) -> impl Iterator<Item = Vec<String>> {
+    InputSource::for_day(day).records()
 }
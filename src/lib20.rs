@@ -1,16 +1,23 @@
+//! The pieces of day 20, Jurassic Jigsaw: square tiles, which may be rotated and flipped, to be
+//! fitted together into a picture by matching their edges.
+
 use num_enum::TryFromPrimitive;
 use strum::IntoEnumIterator;
 use strum_macros::EnumIter;
 
-use array2d::Array2D;
 use itertools::Itertools;
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
 use std::convert::TryFrom;
 use std::fmt;
 use std::ops::Not;
 
-type Grid = [bool; 100];
+use crate::grid::{Grid, ORTHOGONAL};
+use crate::input::records;
+use crate::scan::Scanner;
+use crate::ParseError;
 
+/// A side of a tile, clockwise from the top.
 #[derive(TryFromPrimitive, Debug, Hash, PartialEq, Eq, Clone, Copy)]
 #[repr(u8)]
 pub enum Edge {
@@ -42,6 +49,7 @@ impl Edge {
     }
 }
 
+/// Which way round an edge is read: clockwise or anticlockwise around the tile.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub enum Orientation {
     CW,
@@ -57,6 +65,8 @@ impl Orientation {
     }
 }
 
+/// An edge read one way round. Two tiles fit together along an edge when one's reading clockwise
+/// matches the other's reading anticlockwise.
 #[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
 pub struct OrientedEdge {
     pub e: Edge,
@@ -64,6 +74,7 @@ pub struct OrientedEdge {
 }
 
 impl OrientedEdge {
+    /// The edge this one meets on a neighbouring tile, read the way it must be to match.
     pub fn opposite(&self) -> Self {
         Self {
             e: self.e.opposite(),
@@ -113,76 +124,121 @@ const ORIENTED_EDGES: [OrientedEdge; 8] = [
     },
 ];
 
+/// An edge read as a binary number, its first cell the most significant bit. However long the
+/// edge, it is kept exactly, 64 cells to a word.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EdgeSum(Vec<u64>);
+
+impl EdgeSum {
+    fn read<'a>(cells: impl Iterator<Item = &'a bool>) -> Self {
+        let mut words: Vec<u64> = Vec::new();
+        for (i, &value) in cells.enumerate() {
+            if i % 64 == 0 {
+                words.push(0);
+            }
+            let word = words.last_mut().unwrap();
+            *word = *word << 1 | value as u64;
+        }
+        Self(words)
+    }
+}
+
+impl fmt::Display for EdgeSum {
+    /// Edges of up to 64 cells are shown as a number, longer ones as their words in hex.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_slice() {
+            [word] => write!(f, "{}", word),
+            words => write!(f, "{:x}", words.iter().format(":")),
+        }
+    }
+}
+
+/// A tile as it was scanned, before any rotating or flipping.
 #[derive(Debug)]
 pub struct Tile {
     pub id: usize,
-    grid: Grid,
-    pub edges: HashMap<OrientedEdge, u16>,
-}
-
-lazy_static! {
-    static ref FLIP_10: Vec<u16> = {
-        let flip = |x: u16| {
-            let mut flipped = 0;
-            for i in 0..10 {
-                if x & (1 << i) != 0 {
-                    flipped |= 1 << (9 - i);
-                }
-            }
-            flipped
-        };
-        (0..(1 << 10)).map(flip).collect()
-    };
+    grid: Grid<bool>,
+    /// Each edge, read as a binary number.
+    pub edges: HashMap<OrientedEdge, EdgeSum>,
 }
 
 impl Tile {
-    fn new(id: usize, grid: Grid) -> Self {
+    fn new(id: usize, grid: Grid<bool>) -> Self {
         use Edge::*;
         use Orientation::*;
-        let top_cw = Self::edge_sum(grid[0..10].iter());
-        let right_cw = Self::edge_sum(grid[9..=99].iter().step_by(10));
-        let bottom_cw = Self::edge_sum(grid[90..100].iter().rev());
-        let left_cw = Self::edge_sum(grid[0..=90].iter().step_by(10).rev());
+        let last = grid.width() - 1;
+        let clockwise: [(Edge, Vec<bool>); 4] = [
+            (Top, grid.row(0).copied().collect()),
+            (Right, grid.column(last).copied().collect()),
+            (Bottom, grid.row(last).rev().copied().collect()),
+            (Left, grid.column(0).rev().copied().collect()),
+        ];
 
         let mut edges = HashMap::new();
-        for &(e, cw) in &[
-            (Top, top_cw),
-            (Right, right_cw),
-            (Bottom, bottom_cw),
-            (Left, left_cw),
-        ] {
-            edges.insert(OrientedEdge { e, o: CW }, cw);
-            edges.insert(OrientedEdge { e, o: ACW }, FLIP_10[cw as usize]);
+        for (e, cells) in &clockwise {
+            let e = *e;
+            edges.insert(OrientedEdge { e, o: CW }, EdgeSum::read(cells.iter()));
+            edges.insert(
+                OrientedEdge { e, o: ACW },
+                EdgeSum::read(cells.iter().rev()),
+            );
         }
         Self { id, grid, edges }
     }
 
-    fn edge_sum<'a>(cells: impl Iterator<Item = &'a bool>) -> u16 {
-        let mut sum = 0;
-        for &value in cells {
-            sum <<= 1;
-            sum |= value as u16;
+    /// The length of each side.
+    pub fn size(&self) -> usize {
+        self.grid.width()
+    }
+
+    /// Read a tile from its lines, a record of `input`: the `Tile <id>:` header, then a square
+    /// picture at least 3 wide. Its first row gives its size.
+    pub fn read(input: &str, lines: &[&str]) -> Result<Self, ParseError> {
+        let (header, rows) = lines
+            .split_first()
+            .ok_or_else(|| ParseError::end(input, "a tile"))?;
+        let id = Self::read_header(header).map_err(|e| e.within(input, header))?;
+        if rows.is_empty() {
+            return Err(ParseError::at(input, &header[header.len()..], "a picture"));
         }
-        sum
-    }
-    pub fn read<'a>(mut input: impl Iterator<Item = &'a str>) -> Option<Self> {
-        let id = scan_fmt!(input.next()?, "Tile {d}:", usize).ok()?;
-        let mut grid = [false; 100];
-        for (row, line) in input.take_while(|line| !line.is_empty()).enumerate() {
-            for (col, char) in line.chars().enumerate() {
-                grid[10 * row + col] = match char {
-                    '.' => false,
-                    '#' => true,
-                    _ => panic!(),
-                }
-            }
+        let grid = Grid::from_rows(rows.iter().copied(), ". or #", |c| match c {
+            '.' => Some(false),
+            '#' => Some(true),
+            _ => None,
+        })
+        .map_err(|e| e.within(input, rows[0]))?;
+        let first = rows[0];
+        let size = grid.width();
+        if size < 3 {
+            return Err(ParseError::at(
+                input,
+                &first[first.len()..],
+                "a row at least 3 wide",
+            ));
+        }
+        if let Some(extra) = rows.get(size) {
+            return Err(ParseError::at(input, extra, "a blank line"));
         }
-        Some(Tile::new(id, grid))
+        if grid.height() < size {
+            let last = rows[rows.len() - 1];
+            return Err(ParseError::at(input, &last[last.len()..], "another row"));
+        }
+        Ok(Tile::new(id, grid))
+    }
+
+    fn read_header(header: &str) -> Result<usize, ParseError> {
+        let mut s = Scanner::new(header);
+        s.literal("Tile ")?;
+        let id = s.number()?;
+        s.literal(":")?;
+        s.end()?;
+        Ok(id)
     }
 
+    /// The tile in each of its eight arrangements.
     pub fn arrangements<'a>(&'a self) -> impl Iterator<Item = ArrangedTile<'a>> {
         RotoReflection::iter().map(move |r| ArrangedTile {
-            tile: &self,
+            tile: self,
             arrangement: r,
         })
     }
@@ -206,14 +262,28 @@ impl fmt::Display for Tile {
     }
 }
 
-pub fn read_tiles<'a>(mut input: impl Iterator<Item = &'a str>) -> Vec<Tile> {
-    let mut tiles = Vec::new();
-    while let Some(t) = Tile::read(&mut input) {
-        tiles.push(t);
+/// Reads tiles separated by blank lines, which must all be the same size.
+pub fn read_tiles(input: &str) -> Result<Vec<Tile>, ParseError> {
+    let mut tiles: Vec<Tile> = Vec::new();
+    for r in records(input) {
+        let tile = Tile::read(input, &r)?;
+        if let Some(size) = tiles.first().map(Tile::size) {
+            let row = r[1];
+            if tile.size() > size {
+                return Err(ParseError::at(input, &row[size..], "the end of the row"));
+            }
+            if tile.size() < size {
+                let expected = format!("a row {} wide", size);
+                return Err(ParseError::at(input, &row[row.len()..], expected));
+            }
+        }
+        tiles.push(tile);
     }
-    tiles
+    Ok(tiles)
 }
 
+/// One of the eight ways to rotate and flip a square: a rotation clockwise, after an optional
+/// flip top to bottom. `!r` undoes `r`.
 #[derive(TryFromPrimitive, EnumIter, Clone, Copy, Debug, PartialEq, Eq)]
 #[repr(u8)]
 pub enum RotoReflection {
@@ -259,7 +329,9 @@ impl RotoReflection {
         }
     }
 
-    fn transform(&self, u: usize, v: usize, size: usize) -> (usize, usize) {
+    /// Where the cell `(u, v)` of a `size` by `size` square ends up when the square is arranged
+    /// this way.
+    pub fn transform(&self, u: usize, v: usize, size: usize) -> (usize, usize) {
         let size = size - 1;
         match self {
             RotoReflection::None => (u, v),
@@ -292,6 +364,7 @@ impl Not for RotoReflection {
     }
 }
 
+/// A tile, rotated and flipped.
 #[derive(Clone, Copy)]
 pub struct ArrangedTile<'a> {
     tile: &'a Tile,
@@ -299,16 +372,23 @@ pub struct ArrangedTile<'a> {
 }
 
 impl<'a> ArrangedTile<'a> {
-    fn edge_sum(&self, edge: OrientedEdge) -> u16 {
+    /// The tile before arranging.
+    pub fn tile(&self) -> &'a Tile {
+        self.tile
+    }
+
+    /// An edge of the tile as it is arranged.
+    pub fn edge_sum(&self, edge: OrientedEdge) -> &'a EdgeSum {
         // Work out which of the original tile's oriented edges we want.
         // Think of RotoReflection as the group A4, acting on the oriented edges.
         // We want to apply the inverse of self.arrangement to the edge we are interested in to
         // get the edge of the origianl tile.a
         let inverse = !self.arrangement;
         let orig_edge = inverse.apply(&edge);
-        self.tile.edges[&orig_edge]
+        &self.tile.edges[&orig_edge]
     }
 
+    /// The tile arranged so that its oriented edge `e` ends up where `desired_e` is.
     pub fn such_that(tile: &'a Tile, e: OrientedEdge, desired_e: OrientedEdge) -> Self {
         // Need to find a RotoReflection r such that r.apply(e) == desired_e
         Self {
@@ -342,6 +422,7 @@ impl fmt::Debug for ArrangedTile<'_> {
     }
 }
 
+/// Every pair of distinct elements of a slice, each pair once, in order.
 pub struct Pairs<'a, T> {
     max: usize,
     i: usize,
@@ -377,65 +458,59 @@ impl<'a, T> Iterator for Pairs<'a, T> {
     }
 }
 
+/// Arranged tiles placed on a grid, some of which may be empty. Tiles are only placed where their
+/// edges match their neighbours'.
 pub struct Composition<'a> {
-    tiles: Array2D<Option<ArrangedTile<'a>>>,
+    tiles: Grid<Option<ArrangedTile<'a>>>,
     pub ids: HashSet<usize>,
 }
 
 impl<'a> Composition<'a> {
     pub fn new(width: usize, height: usize) -> Self {
         Self {
-            tiles: Array2D::filled_with(None, width, height),
+            tiles: Grid::filled(width, height, None),
             ids: HashSet::new(),
         }
     }
 
-    fn get(&self, x: isize, y: isize) -> Option<ArrangedTile> {
-        if 0 <= x
-            && x < self.tiles.num_columns() as isize
-            && 0 <= y
-            && y < self.tiles.num_rows() as isize
-        {
-            *self.tiles.get(x as usize, y as usize).unwrap()
-        } else {
-            None
-        }
+    /// How many tiles across it is.
+    pub fn width(&self) -> usize {
+        self.tiles.width()
+    }
+
+    /// How many tiles down it is.
+    pub fn height(&self) -> usize {
+        self.tiles.height()
+    }
+
+    fn get(&self, x: isize, y: isize) -> Option<ArrangedTile<'_>> {
+        self.tiles.get(x, y).copied().flatten()
     }
 
-    pub fn get_edge_sum(&self, x: usize, y: usize, e: OrientedEdge) -> Option<u16> {
-        let t = self.tiles.get(x, y).unwrap();
-        t.map(|t| t.edge_sum(e))
+    /// An edge of the tile at `(x, y)`, as arranged, if there is a tile there.
+    pub fn get_edge_sum(&self, x: usize, y: usize, e: OrientedEdge) -> Option<&'a EdgeSum> {
+        self.tiles[(x, y)].map(|t| t.edge_sum(e))
     }
 
+    /// Takes away the tile at `(x, y)`, if there is one.
     pub fn clear(&mut self, x: usize, y: usize) {
-        println!(
-            "Remove {} from ({},{})",
-            self.tiles.get(x, y).unwrap().unwrap().tile.id,
-            x,
-            y
-        );
-        self.ids
-            .remove(&self.tiles.get(x, y).unwrap().unwrap().tile.id);
-        self.tiles.set(x, y, None).unwrap();
+        if let Some(t) = self.tiles[(x, y)].take() {
+            trace!("Remove {} from ({},{})", t.tile.id, x, y);
+            self.ids.remove(&t.tile.id);
+        }
     }
 
-    fn neighbours(&self, x: usize, y: usize) -> Vec<(Edge, ArrangedTile)> {
+    fn neighbours(&self, x: usize, y: usize) -> Vec<(Edge, ArrangedTile<'_>)> {
         use Edge::*;
         let (x, y) = (x as isize, y as isize);
-        let mut neighbours = Vec::new();
-        for &(x1, y1, dir) in &[
-            (x, y - 1, Top),
-            (x + 1, y, Right),
-            (x, y + 1, Bottom),
-            (x - 1, y, Left),
-        ] {
-            if let Some(t) = self.get(x1, y1) {
-                neighbours.push((dir, t));
-            }
-        }
-        neighbours
+        ORTHOGONAL
+            .iter()
+            .zip(&[Top, Right, Bottom, Left])
+            .filter_map(|(&(dx, dy), &dir)| self.get(x + dx, y + dy).map(|t| (dir, t)))
+            .collect()
     }
 
+    /// Places the tile at `(x, y)` if it matches the tiles around it, and says whether it did.
     pub fn try_insert(&mut self, t: ArrangedTile<'a>, x: usize, y: usize) -> bool {
         for (dir, t2) in self.neighbours(x, y) {
             let e = OrientedEdge {
@@ -447,7 +522,7 @@ impl<'a> Composition<'a> {
             }
         }
         self.ids.insert(t.tile.id);
-        self.tiles.set(x, y, Some(t)).unwrap();
+        self.tiles[(x, y)] = Some(t);
         true
     }
 
@@ -456,39 +531,42 @@ impl<'a> Composition<'a> {
         self.ids.contains(&id)
     }
 
+    /// The product of the corner tiles' ids, each counted once however narrow the composition.
+    /// The corners mustn't be empty.
     pub fn corners(&self) -> usize {
-        let x = [0, self.tiles.num_columns() - 1];
-        let y = [0, self.tiles.num_rows() - 1];
+        let x = [0, self.tiles.width() - 1];
+        let y = [0, self.tiles.height() - 1];
         x.iter()
             .cartesian_product(y.iter())
-            .map(|(x, y)| self.tiles.get(*x, *y).unwrap().unwrap().tile.id)
+            .unique()
+            .map(|(&x, &y)| self.tiles[(x, y)].unwrap().tile.id)
             .product()
     }
 
-    pub fn assemble(&self) -> Array2D<bool> {
-        let width = self.tiles.num_columns() * 8;
-        let height = self.tiles.num_rows() * 8;
-        let mut image = Array2D::filled_with(false, width, height);
-        for (x, y) in (0..self.tiles.num_columns()).cartesian_product(0..self.tiles.num_rows()) {
-            let src = &self.tiles.get(x, y).unwrap().unwrap();
-            for (u, v) in (0..8).cartesian_product(0..8) {
-                let (src_u, src_v) = (!src.arrangement).transform(u, v, 8);
-                let i = 10 * src_v + src_u;
-                image.set(8 * x + u, 8 * y + v, src.tile.grid[i]).unwrap();
-            }
-        }
-        image
+    /// The picture with every tile's border stripped off. There mustn't be any empty places.
+    pub fn assemble(&self) -> Grid<bool> {
+        let size = self.tiles[(0, 0)].unwrap().tile.size();
+        let inside = size - 2;
+        let (width, height) = (self.tiles.width(), self.tiles.height());
+        Grid::from_fn(width * inside, height * inside, |x, y| {
+            let src = self.tiles[(x / inside, y / inside)].unwrap();
+            let (u, v) = (x % inside, y % inside);
+            let (src_u, src_v) = (!src.arrangement).transform(u + 1, v + 1, size);
+            src.tile.grid[(src_u, src_v)]
+        })
     }
 }
 
+/// A view of a square grid, rotated and flipped.
 pub struct ArrangedSquareBitmap<'a> {
     arrangement: RotoReflection,
-    src: &'a Array2D<bool>,
+    src: &'a Grid<bool>,
 }
 
 impl<'a> ArrangedSquareBitmap<'a> {
-    pub fn new(arr: RotoReflection, src: &'a Array2D<bool>) -> Option<Self> {
-        if src.num_rows() == src.num_columns() {
+    /// The view of `src` through `arr`, unless `src` isn't square.
+    pub fn new(arr: RotoReflection, src: &'a Grid<bool>) -> Option<Self> {
+        if src.width() == src.height() {
             Some(Self {
                 arrangement: arr,
                 src,
@@ -498,30 +576,36 @@ impl<'a> ArrangedSquareBitmap<'a> {
         }
     }
 
+    /// The length of each side.
+    pub fn size(&self) -> usize {
+        self.src.width()
+    }
+
     pub fn get(&self, x: usize, y: usize) -> Option<&bool> {
-        let (src_x, src_y) = (!self.arrangement).transform(x, y, self.src.num_columns());
-        println!("{}, {}", src_x, src_y);
-        self.src.get(src_x, src_y)
+        let (src_x, src_y) = (!self.arrangement).transform(x, y, self.src.width());
+        self.src.get(src_x as isize, src_y as isize)
     }
 }
 
-pub fn sqrt(n: usize) -> Option<usize> {
-    let fsqrt = (n as f64).sqrt();
-    let isqrt = fsqrt as usize;
-    if isqrt * isqrt == n {
-        Some(isqrt)
-    } else {
-        None
-    }
+/// Every `(width, height)` with `width * height == n`, squarest first, and wider first when two
+/// are as square as each other.
+pub fn shapes(n: usize) -> Vec<(usize, usize)> {
+    let mut shapes: Vec<_> = (1..=n)
+        .filter(|width| n.is_multiple_of(*width))
+        .map(|width| (width, n / width))
+        .collect();
+    shapes.sort_by_key(|&(width, height)| (width.abs_diff(height), Reverse(width)));
+    shapes
 }
 
-pub type EdgeLookup<'a> = HashMap<u16, Vec<(OrientedEdge, &'a Tile)>>;
+/// Every oriented edge of every tile, by its value.
+pub type EdgeLookup<'a> = HashMap<EdgeSum, Vec<(OrientedEdge, &'a Tile)>>;
 
-pub fn build_edge_lookup(tiles: &[Tile]) -> EdgeLookup {
+pub fn build_edge_lookup(tiles: &[Tile]) -> EdgeLookup<'_> {
     let mut map = HashMap::new();
     for t in tiles {
         for (e, value) in t.edges.iter() {
-            let v = map.entry(*value).or_insert(Vec::new());
+            let v = map.entry(value.clone()).or_insert(Vec::new());
             v.push((*e, t));
         }
     }
@@ -533,9 +617,73 @@ pub fn build_edge_lookup(tiles: &[Tile]) -> EdgeLookup {
     map
 }
 
+/// Where a tile can go, judging by which of its edges match no other tile's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Placement {
+    /// Two adjacent edges match nothing. At the ends of a composition one tile wide, three do,
+    /// and a lone tile has four.
+    Corner,
+    /// One edge matches nothing, or two opposite ones in a composition one tile wide.
+    Border,
+    /// Every edge matches something.
+    Interior,
+}
+
+/// What the edge lookup says about the tiles, before any are placed.
+pub struct Analysis<'a> {
+    /// Whether every edge that matches another tile's matches exactly one, exactly one way. If
+    /// so, the tiles only fit together one way, give or take turning the whole picture over.
+    pub unique: bool,
+    /// Each tile with where it can go, in the order given.
+    pub placements: Vec<(&'a Tile, Placement)>,
+}
+
+impl<'a> Analysis<'a> {
+    /// Classifies every tile by looking each of its edges up once.
+    pub fn new(tiles: &'a [Tile], edge_lookup: &EdgeLookup<'a>) -> Self {
+        let unique = edge_lookup
+            .values()
+            .all(|v| v.len() == 2 && v[0].1.id != v[1].1.id);
+        let placements = tiles
+            .iter()
+            .map(|tile| {
+                let unmatched: Vec<Edge> = ORIENTED_EDGES
+                    .iter()
+                    .filter(|e| e.o == Orientation::CW && !matches_another(tile, e, edge_lookup))
+                    .map(|e| e.e)
+                    .collect();
+                let placement = match unmatched.as_slice() {
+                    [] => Placement::Interior,
+                    [_] => Placement::Border,
+                    [a, b] if a.opposite() == *b => Placement::Border,
+                    _ => Placement::Corner,
+                };
+                (tile, placement)
+            })
+            .collect();
+        Self { unique, placements }
+    }
+
+    /// The tiles which can only go in a corner.
+    pub fn corners(&self) -> impl Iterator<Item = &'a Tile> + '_ {
+        self.placements
+            .iter()
+            .filter(|(_, p)| *p == Placement::Corner)
+            .map(|(t, _)| *t)
+    }
+}
+
+fn matches_another(tile: &Tile, e: &OrientedEdge, edge_lookup: &EdgeLookup) -> bool {
+    edge_lookup
+        .get(&tile.edges[e])
+        .is_some_and(|v| v.iter().any(|(_, t)| t.id != tile.id))
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::generate::Prng;
+    use rand::{Rng, SeedableRng};
 
     #[test]
     fn test_pairs() {
@@ -561,9 +709,12 @@ Tile 2311:
 ..#....#..
 ###...#.#.
 ..###..###";
-        let tiles = read_tiles(input.split("\n"));
+        let tiles = read_tiles(input).unwrap();
         let check = |e, o, v| {
-            assert_eq!(tiles[0].edges.get(&OrientedEdge { e, o }).unwrap(), &v);
+            assert_eq!(
+                tiles[0].edges.get(&OrientedEdge { e, o }).unwrap(),
+                &EdgeSum(vec![v])
+            );
         };
         use Edge::*;
         use Orientation::*;
@@ -578,13 +729,112 @@ Tile 2311:
         check(Right, ACW, 0b1001101000);
     }
 
+    #[test]
+    fn test_read_errors() {
+        let picture = "..........\n".repeat(10);
+        let error = |input: &str| {
+            let e = read_tiles(input).unwrap_err();
+            (e.line, e.column, e.expected, e.found)
+        };
+        let input = format!("Tile 1:\n{}\nTile x2:\n{}", picture, picture);
+        assert_eq!(error(&input), (13, 6, "a number".into(), "x2:".into()));
+        let input = format!("Tile 1:\n{}.....#....\n", picture);
+        assert_eq!(
+            error(&input),
+            (12, 1, "a blank line".into(), ".....#....".into())
+        );
+        let input = format!("Tile 1:\n{}", picture.replacen('.', "X", 1));
+        assert_eq!(error(&input), (2, 1, ". or #".into(), "X".into()));
+        let input = format!("Tile 1:\n{}", &picture[11..]);
+        assert_eq!(error(&input), (10, 11, "another row".into(), "".into()));
+        let input = format!("Tile 1:\n{}\nTile 2:\n{}", picture, "...\n".repeat(3));
+        assert_eq!(error(&input), (14, 4, "a row 10 wide".into(), "".into()));
+        let input = "Tile 1:\n..\n..\n";
+        assert_eq!(
+            error(input),
+            (2, 3, "a row at least 3 wide".into(), "".into())
+        );
+    }
+
+    #[test]
+    fn analysis() {
+        use crate::generate::{jigsaw, Mode};
+
+        let mut rng = Prng::seed_from_u64(25);
+        let count = |analysis: &Analysis, placement| {
+            analysis
+                .placements
+                .iter()
+                .filter(|(_, p)| *p == placement)
+                .count()
+        };
+        for &(width, height, corners, borders) in &[(4, 3, 4, 6), (1, 4, 2, 2), (1, 1, 1, 0)] {
+            let generated = jigsaw(&mut rng, width, height, 10, Mode::Known);
+            let tiles = read_tiles(&generated.input).unwrap();
+            let lookup = build_edge_lookup(&tiles);
+            let analysis = Analysis::new(&tiles, &lookup);
+            assert!(analysis.unique);
+            assert_eq!(count(&analysis, Placement::Corner), corners);
+            assert_eq!(count(&analysis, Placement::Border), borders);
+            assert_eq!(
+                count(&analysis, Placement::Interior),
+                width * height - corners - borders
+            );
+            let product: usize = analysis.corners().map(|t| t.id).product();
+            assert_eq!(generated.expected[0].1, product.to_string());
+        }
+
+        // A second copy of a tile matches everything the first does.
+        let generated = jigsaw(&mut rng, 2, 2, 10, Mode::Known);
+        let mut tiles = read_tiles(&generated.input).unwrap();
+        tiles.push(Tile::new(1, tiles[0].grid.clone()));
+        let lookup = build_edge_lookup(&tiles);
+        assert!(!Analysis::new(&tiles, &lookup).unique);
+    }
+
+    #[test]
+    fn test_shapes() {
+        assert_eq!(
+            shapes(12),
+            vec![(4, 3), (3, 4), (6, 2), (2, 6), (12, 1), (1, 12)]
+        );
+        assert_eq!(shapes(9), vec![(3, 3), (9, 1), (1, 9)]);
+        assert_eq!(shapes(7), vec![(7, 1), (1, 7)]);
+        assert_eq!(shapes(1), vec![(1, 1)]);
+    }
+
+    #[test]
+    fn wide_edges() {
+        let mut rng = Prng::seed_from_u64(22);
+        for &size in &[3, 16, 17, 64, 65, 130] {
+            let tile = Tile::new(1, Grid::from_fn(size, size, |_, _| rng.gen()));
+            assert_eq!(tile.size(), size);
+            for e in &ORIENTED_EDGES {
+                let EdgeSum(words) = &tile.edges[e];
+                assert_eq!(words.len(), size.div_ceil(64));
+                let bits: usize = words.iter().map(|w| 64 - w.leading_zeros() as usize).sum();
+                assert!(bits <= size);
+            }
+            // The top edge read clockwise is the top row left to right.
+            let top = &tile.edges[&ORIENTED_EDGES[0]];
+            assert_eq!(top, &EdgeSum::read(tile.grid.row(0)));
+            assert_eq!(
+                &tile.edges[&ORIENTED_EDGES[1]],
+                &EdgeSum::read(tile.grid.row(0).rev())
+            );
+        }
+        let ones = EdgeSum::read([true; 66].iter());
+        assert_eq!(ones.to_string(), "ffffffffffffffff:3");
+        assert_eq!(EdgeSum::read([true, false, true].iter()).to_string(), "5");
+    }
+
     #[test]
     fn test_get_edge() {
-        let mut g = [false; 100];
-        g[1] = true;
-        g[29] = true;
-        g[96] = true;
-        g[50] = true;
+        let mut g = Grid::filled(10, 10, false);
+        g[(1, 0)] = true;
+        g[(9, 2)] = true;
+        g[(6, 9)] = true;
+        g[(0, 5)] = true;
         let t = Tile::new(0, g);
         assert_eq!(t.to_string(), "#0(T256/2 R128/4 B64/8 L32/16)");
 
@@ -634,4 +884,70 @@ Tile 2311:
         check(Left, CW, Bottom, ACW, VFlipCW270);
         check(Bottom, ACW, Left, CW, VFlipCW270);
     }
+
+    #[test]
+    fn inverse_undoes_arrangement() {
+        const SIZE: usize = 8;
+        for r in RotoReflection::iter() {
+            for &e in ORIENTED_EDGES.iter() {
+                assert_eq!(
+                    (!r).apply(&r.apply(&e)),
+                    e,
+                    "{:?} then {:?} moves {}",
+                    r,
+                    !r,
+                    e
+                );
+            }
+
+            for (u, v) in (0..SIZE).cartesian_product(0..SIZE) {
+                let (x, y) = r.transform(u, v, SIZE);
+                assert_eq!((!r).transform(x, y, SIZE), (u, v), "{:?}", r);
+
+                // A bitmap with just this cell set only comes back the same if the cell does.
+                let grid = Grid::from_fn(SIZE, SIZE, |x, y| (x, y) == (u, v));
+                let arranged = Grid::from_fn(SIZE, SIZE, |x, y| {
+                    *ArrangedSquareBitmap::new(r, &grid)
+                        .unwrap()
+                        .get(x, y)
+                        .unwrap()
+                });
+                let restored = ArrangedSquareBitmap::new(!r, &arranged).unwrap();
+                for (x, y) in grid.positions() {
+                    assert_eq!(
+                        restored.get(x, y),
+                        Some(&grid[(x, y)]),
+                        "{:?} with ({}, {}) set",
+                        r,
+                        u,
+                        v
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn such_that_maps_source_to_target() {
+        // Lopsided enough that no two of its oriented edges read the same.
+        let tile = Tile::new(1, Grid::from_fn(10, 10, |x, y| (x * 7 + y * 3) % 11 < 4));
+        let sums: Vec<_> = ORIENTED_EDGES.iter().map(|e| &tile.edges[e]).collect();
+        assert_eq!(sums.iter().unique().count(), ORIENTED_EDGES.len());
+
+        for (&src, &dst) in ORIENTED_EDGES
+            .iter()
+            .cartesian_product(ORIENTED_EDGES.iter())
+        {
+            let arranged = ArrangedTile::such_that(&tile, src, dst);
+            assert_eq!(arranged.arrangement.apply(&src), dst);
+            assert_eq!(
+                arranged.edge_sum(dst),
+                &tile.edges[&src],
+                "{} to {} with {:?}",
+                src,
+                dst,
+                arranged.arrangement
+            );
+        }
+    }
 }
@@ -0,0 +1,452 @@
+//! Patterns to look for in pictures of on and off cells, like day 20's sea monster. A pattern is
+//! drawn with `#` for cells which must be on, `.` for cells which must be off, and a space or `?`
+//! for cells which don't matter. Short lines are padded with cells which don't matter.
+//!
+//! Matching works a row at a time on bitmasks: for each row of the picture, the columns at which
+//! the pattern could start are whittled down by one shift and one `and` per cell of the pattern,
+//! sixty-four columns at a time.
+
+use std::fs;
+use std::path::Path;
+
+use array2d::Array2D;
+use strum::IntoEnumIterator;
+
+use crate::grid::Grid;
+use crate::lib20::{ArrangedSquareBitmap, RotoReflection};
+use crate::{Error, ParseError};
+
+/// A picture of cells which are on or off.
+pub trait Bitmap {
+    fn width(&self) -> usize;
+    fn height(&self) -> usize;
+    /// Whether the cell is on. The cell must be within the picture.
+    fn is_on(&self, x: usize, y: usize) -> bool;
+}
+
+impl Bitmap for Grid<bool> {
+    fn width(&self) -> usize {
+        Grid::width(self)
+    }
+
+    fn height(&self) -> usize {
+        Grid::height(self)
+    }
+
+    fn is_on(&self, x: usize, y: usize) -> bool {
+        self[(x, y)]
+    }
+}
+
+impl Bitmap for Array2D<bool> {
+    fn width(&self) -> usize {
+        self.num_columns()
+    }
+
+    fn height(&self) -> usize {
+        self.num_rows()
+    }
+
+    fn is_on(&self, x: usize, y: usize) -> bool {
+        self[(y, x)]
+    }
+}
+
+impl Bitmap for ArrangedSquareBitmap<'_> {
+    fn width(&self) -> usize {
+        self.size()
+    }
+
+    fn height(&self) -> usize {
+        self.size()
+    }
+
+    fn is_on(&self, x: usize, y: usize) -> bool {
+        *self.get(x, y).unwrap()
+    }
+}
+
+/// A set of columns: column `x` is bit `x % 64` of word `x / 64`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Row(Vec<u64>);
+
+impl Row {
+    fn words(width: usize) -> usize {
+        width.div_ceil(64)
+    }
+
+    /// Columns `0..n`, in a row wide enough for `width` columns.
+    fn first(n: usize, width: usize) -> Self {
+        let mut words = vec![0; Self::words(width)];
+        for (i, word) in words.iter_mut().enumerate() {
+            let bits = n.saturating_sub(64 * i).min(64);
+            *word = if bits == 64 { !0 } else { (1 << bits) - 1 };
+        }
+        Row(words)
+    }
+
+    fn of(image: &impl Bitmap, y: usize, on: bool) -> Self {
+        let mut words = vec![0; Self::words(image.width())];
+        for x in 0..image.width() {
+            if image.is_on(x, y) == on {
+                words[x / 64] |= 1 << (x % 64);
+            }
+        }
+        Row(words)
+    }
+
+    /// Keeps the columns `x` for which column `x + n` of `other` is set.
+    fn and_shifted(&mut self, other: &Row, n: usize) {
+        let (skip, bits) = (n / 64, n % 64);
+        for i in 0..self.0.len() {
+            let low = other.0.get(i + skip).copied().unwrap_or(0);
+            let high = other.0.get(i + skip + 1).copied().unwrap_or(0);
+            let shifted = match bits {
+                0 => low,
+                _ => low >> bits | high << (64 - bits),
+            };
+            self.0[i] &= shifted;
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.0.iter().all(|&w| w == 0)
+    }
+
+    fn columns(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().enumerate().flat_map(|(i, &word)| {
+            (0..64)
+                .filter(move |bit| word & (1 << bit) != 0)
+                .map(move |bit| 64 * i + bit)
+        })
+    }
+}
+
+/// A picture as rows of bitmasks, ready to be searched as many times as needed.
+struct Rows {
+    width: usize,
+    height: usize,
+    on: Vec<Row>,
+    off: Vec<Row>,
+}
+
+impl Rows {
+    fn new(image: &impl Bitmap) -> Self {
+        let height = image.height();
+        Self {
+            width: image.width(),
+            height,
+            on: (0..height).map(|y| Row::of(image, y, true)).collect(),
+            off: (0..height).map(|y| Row::of(image, y, false)).collect(),
+        }
+    }
+}
+
+/// A rectangle of cells which must be on, cells which must be off, and cells which don't matter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    width: usize,
+    height: usize,
+    /// The cells which must be on, and those which must be off, in order.
+    on: Vec<(usize, usize)>,
+    off: Vec<(usize, usize)>,
+}
+
+/// Somewhere a pattern was found.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Match {
+    /// The top left corner of the arranged pattern.
+    pub x: usize,
+    pub y: usize,
+    /// How the pattern is turned, compared to how it was drawn.
+    pub arrangement: RotoReflection,
+}
+
+/// Everywhere a pattern was found, in any arrangement.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Search {
+    pub matches: Vec<Match>,
+    /// The cells under a `#` of at least one match.
+    pub covered: Grid<bool>,
+}
+
+impl Pattern {
+    /// Reads a drawing of the pattern. Blank lines at the end are ignored.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let lines: Vec<&str> = input.lines().collect();
+        let height = lines
+            .iter()
+            .rposition(|line| !line.trim().is_empty())
+            .map_or(0, |last| last + 1);
+        let mut width = 0;
+        let (mut on, mut off) = (Vec::new(), Vec::new());
+        for (y, line) in lines[..height].iter().enumerate() {
+            for (x, (i, c)) in line.char_indices().enumerate() {
+                match c {
+                    '#' => on.push((x, y)),
+                    '.' => off.push((x, y)),
+                    ' ' | '?' => {}
+                    _ => {
+                        let found = &line[i..i + c.len_utf8()];
+                        return Err(ParseError::at(input, found, "#, . or a space or ?"));
+                    }
+                }
+                width = width.max(x + 1);
+            }
+        }
+        if on.is_empty() && off.is_empty() {
+            return Err(ParseError::end(input, "a # or ."));
+        }
+        on.sort_unstable_by_key(|&(x, y)| (y, x));
+        off.sort_unstable_by_key(|&(x, y)| (y, x));
+        Ok(Self {
+            width,
+            height,
+            on,
+            off,
+        })
+    }
+
+    /// Reads a pattern from a file.
+    pub fn read(path: &Path) -> crate::Result<Self> {
+        let text = fs::read_to_string(path).map_err(|source| Error::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        Self::parse(&text).map_err(|e| Error::Parse(format!("{}: {}", path.display(), e)))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The pattern turned the way `arrangement` turns a square.
+    pub fn arranged(&self, arrangement: RotoReflection) -> Self {
+        let size = self.width.max(self.height);
+        let corners = [(0, 0), (self.width - 1, self.height - 1)]
+            .map(|(x, y)| arrangement.transform(x, y, size));
+        let left = corners[0].0.min(corners[1].0);
+        let top = corners[0].1.min(corners[1].1);
+        let arrange = |cells: &[(usize, usize)]| {
+            let mut cells: Vec<_> = cells
+                .iter()
+                .map(|&(x, y)| {
+                    let (x, y) = arrangement.transform(x, y, size);
+                    (x - left, y - top)
+                })
+                .collect();
+            cells.sort_unstable_by_key(|&(x, y)| (y, x));
+            cells
+        };
+        Self {
+            width: corners[0].0.abs_diff(corners[1].0) + 1,
+            height: corners[0].1.abs_diff(corners[1].1) + 1,
+            on: arrange(&self.on),
+            off: arrange(&self.off),
+        }
+    }
+
+    /// The top left corner of everywhere the pattern appears the way up it was drawn, row by row.
+    pub fn positions(&self, image: &impl Bitmap) -> Vec<(usize, usize)> {
+        self.positions_in(&Rows::new(image))
+    }
+
+    fn positions_in(&self, rows: &Rows) -> Vec<(usize, usize)> {
+        if self.width > rows.width || self.height > rows.height {
+            return Vec::new();
+        }
+        let starts = Row::first(rows.width - self.width + 1, rows.width);
+        let mut positions = Vec::new();
+        for y in 0..=rows.height - self.height {
+            let mut candidates = starts.clone();
+            let cells = self.on.iter().map(|&(dx, dy)| (&rows.on[y + dy], dx));
+            let cells = cells.chain(self.off.iter().map(|&(dx, dy)| (&rows.off[y + dy], dx)));
+            for (row, dx) in cells {
+                candidates.and_shifted(row, dx);
+                if candidates.is_empty() {
+                    break;
+                }
+            }
+            positions.extend(candidates.columns().map(|x| (x, y)));
+        }
+        positions
+    }
+
+    /// Marks the cells under the pattern's `#`s, with its top left corner at `(x, y)`.
+    pub fn cover(&self, covered: &mut Grid<bool>, x: usize, y: usize) {
+        for &(dx, dy) in &self.on {
+            covered[(x + dx, y + dy)] = true;
+        }
+    }
+
+    /// Looks for the pattern in all eight of its arrangements. An arrangement which looks the same
+    /// as an earlier one, because the pattern is symmetric, is skipped, so that nothing is found
+    /// twice.
+    pub fn search(&self, image: &impl Bitmap) -> Search {
+        let rows = Rows::new(image);
+        let mut covered = Grid::filled(rows.width, rows.height, false);
+        let mut matches = Vec::new();
+        let mut tried: Vec<Pattern> = Vec::new();
+        for arrangement in RotoReflection::iter() {
+            let arranged = self.arranged(arrangement);
+            if tried.contains(&arranged) {
+                continue;
+            }
+            for (x, y) in arranged.positions_in(&rows) {
+                arranged.cover(&mut covered, x, y);
+                matches.push(Match { x, y, arrangement });
+            }
+            tried.push(arranged);
+        }
+        Search { matches, covered }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::generate::Prng;
+    use rand::{Rng, SeedableRng};
+
+    fn picture(rows: &str) -> Grid<bool> {
+        Grid::parse(rows, ". or #", |c| Some(c == '#')).unwrap()
+    }
+
+    #[test]
+    fn parsing() {
+        let pattern = Pattern::parse("#.\n ?#\n\n\n").unwrap();
+        assert_eq!((pattern.width(), pattern.height()), (3, 2));
+        assert_eq!(pattern.on, vec![(0, 0), (2, 1)]);
+        assert_eq!(pattern.off, vec![(1, 0)]);
+
+        let e = Pattern::parse("#.\n#x").unwrap_err();
+        assert_eq!((e.line, e.column, e.found.as_str()), (2, 2, "x"));
+        assert!(Pattern::parse(" ?\n").is_err());
+    }
+
+    #[test]
+    fn arrangements() {
+        // #..
+        // ##
+        let pattern = Pattern::parse("#..\n##").unwrap();
+        let turned = pattern.arranged(RotoReflection::CW90);
+        assert_eq!((turned.width(), turned.height()), (2, 3));
+        assert_eq!(turned.on, vec![(0, 0), (1, 0), (0, 1)]);
+        assert_eq!(turned.off, vec![(1, 1), (1, 2)]);
+        for r in RotoReflection::iter() {
+            assert_eq!(pattern.arranged(r).arranged(!r), pattern);
+        }
+    }
+
+    #[test]
+    fn search_every_arrangement() {
+        let image = picture(
+            "\
+#.....
+##..##
+....#.
+......
+.#....
+##....",
+        );
+        let pattern = Pattern::parse("#.\n##").unwrap();
+        let search = pattern.search(&image);
+        assert_eq!(
+            search.matches,
+            vec![
+                Match {
+                    x: 0,
+                    y: 0,
+                    arrangement: RotoReflection::None
+                },
+                Match {
+                    x: 4,
+                    y: 1,
+                    arrangement: RotoReflection::CW90
+                },
+                Match {
+                    x: 0,
+                    y: 4,
+                    arrangement: RotoReflection::CW270
+                },
+            ]
+        );
+        assert_eq!(search.covered, image);
+
+        // Wildcards match anything, but dots only match cells which are off.
+        assert_eq!(
+            Pattern::parse("#?\n##").unwrap().positions(&image),
+            vec![(0, 0)]
+        );
+        assert_eq!(Pattern::parse("#\n#").unwrap().positions(&image).len(), 3);
+    }
+
+    #[test]
+    fn symmetric_patterns_are_found_once() {
+        let image = picture("...\n.#.\n...");
+        let search = Pattern::parse("...\n.#.\n...").unwrap().search(&image);
+        assert_eq!(search.matches.len(), 1);
+    }
+
+    #[test]
+    fn other_bitmaps() {
+        let rows = vec![vec![false, true, true], vec![false, false, true]];
+        let array = Array2D::from_rows(&rows);
+        let pattern = Pattern::parse("##\n.#").unwrap();
+        assert_eq!(pattern.positions(&array), vec![(1, 0)]);
+
+        let square = picture("##.\n#..\n...");
+        let view = ArrangedSquareBitmap::new(RotoReflection::CW90, &square).unwrap();
+        assert_eq!(pattern.positions(&view), vec![(1, 0)]);
+    }
+
+    /// Checks every cell of every position, the slow way.
+    fn naive_positions(pattern: &Pattern, image: &Grid<bool>) -> Vec<(usize, usize)> {
+        let mut positions = Vec::new();
+        for y in 0..(image.height() + 1).saturating_sub(pattern.height) {
+            for x in 0..(image.width() + 1).saturating_sub(pattern.width) {
+                let on = pattern.on.iter().all(|&(dx, dy)| image[(x + dx, y + dy)]);
+                let off = pattern.off.iter().all(|&(dx, dy)| !image[(x + dx, y + dy)]);
+                if on && off {
+                    positions.push((x, y));
+                }
+            }
+        }
+        positions
+    }
+
+    #[test]
+    fn agrees_with_naive_search() {
+        let mut rng = Prng::seed_from_u64(22);
+        for _ in 0..200 {
+            // Wide enough that patterns straddle the words of a row.
+            let (width, height) = (rng.gen_range(1..200), rng.gen_range(1..12));
+            let image = Grid::from_fn(width, height, |_, _| rng.gen_bool(0.7));
+            let (pw, ph) = (rng.gen_range(1..80), rng.gen_range(1..4));
+            let drawing: Vec<String> = (0..ph)
+                .map(|_| {
+                    (0..pw)
+                        .map(|_| match rng.gen_range(0..10) {
+                            0 => '.',
+                            1..=3 => '#',
+                            _ => '?',
+                        })
+                        .collect()
+                })
+                .collect();
+            let Ok(pattern) = Pattern::parse(&drawing.join("\n")) else {
+                continue;
+            };
+            assert_eq!(
+                pattern.positions(&image),
+                naive_positions(&pattern, &image),
+                "{:?}",
+                drawing
+            );
+        }
+    }
+}
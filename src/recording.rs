@@ -0,0 +1,221 @@
+//! Recordings of the cellular automata, one frame per generation, for watching how a layout
+//! evolves rather than only seeing where it ends up. A recording can be played back in a terminal
+//! or saved as text.
+
+use std::fmt::{self, Display};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use crate::days::Part;
+use crate::{day11, day17, ParseError};
+
+/// One generation: what it was, and a drawing of it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Frame {
+    pub title: String,
+    pub picture: String,
+}
+
+/// Frames in the order they happened.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Recording {
+    pub frames: Vec<Frame>,
+}
+
+// ANSI escape sequences. Each frame is drawn over the last one rather than clearing the screen,
+// which would flicker.
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+const CLEAR_SCREEN: &str = "\x1b[2J";
+const HOME: &str = "\x1b[H";
+const CLEAR_LINE: &str = "\x1b[K";
+const CLEAR_BELOW: &str = "\x1b[J";
+
+impl Recording {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a frame, drawn with `picture`'s `Display`.
+    pub fn record(&mut self, title: impl Into<String>, picture: &impl Display) {
+        self.frames.push(Frame {
+            title: title.into(),
+            picture: picture.to_string().trim_end().to_owned(),
+        });
+    }
+
+    /// Every frame as plain text: its title underlined, then its picture, with a blank line
+    /// between frames.
+    pub fn export(&self) -> String {
+        let mut text = String::new();
+        for (i, frame) in self.frames.iter().enumerate() {
+            if i > 0 {
+                text.push('\n');
+            }
+            text.push_str(&frame.title);
+            text.push('\n');
+            text.push_str(&"-".repeat(frame.title.chars().count()));
+            text.push('\n');
+            text.push_str(&frame.picture);
+            text.push('\n');
+        }
+        text
+    }
+
+    /// Draws each frame in turn at the top of the terminal, waiting `delay` after each one. The
+    /// last frame is left on the screen.
+    pub fn play(&self, out: &mut impl Write, delay: Duration) -> io::Result<()> {
+        write!(out, "{}{}", HIDE_CURSOR, CLEAR_SCREEN)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            write!(out, "{}", HOME)?;
+            let counter = format!("[{}/{}]", i + 1, self.frames.len());
+            writeln!(out, "{} {}{}", frame.title, counter, CLEAR_LINE)?;
+            for line in frame.picture.lines() {
+                writeln!(out, "{}{}", line, CLEAR_LINE)?;
+            }
+            write!(out, "{}", CLEAR_BELOW)?;
+            out.flush()?;
+            thread::sleep(delay);
+        }
+        write!(out, "{}", SHOW_CURSOR)?;
+        out.flush()
+    }
+}
+
+impl Display for Recording {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.export())
+    }
+}
+
+/// A day whose generations can be recorded.
+pub struct Recorder {
+    pub day: usize,
+    record: fn(&str, Part) -> Result<Recording, ParseError>,
+}
+
+impl Recorder {
+    /// Parses `input` and records every generation the part goes through, starting with the
+    /// input itself.
+    pub fn record(&self, input: &str, part: Part) -> Result<Recording, ParseError> {
+        (self.record)(input, part)
+    }
+}
+
+pub static RECORDERS: [Recorder; 2] = [
+    Recorder {
+        day: 11,
+        record: seating,
+    },
+    Recorder {
+        day: 17,
+        record: conway_cubes,
+    },
+];
+
+pub fn find(day: usize) -> Option<&'static Recorder> {
+    RECORDERS.iter().find(|r| r.day == day)
+}
+
+fn seating(input: &str, part: Part) -> Result<Recording, ParseError> {
+    use day11::{Layout, Tile};
+    let start = Layout::parse(input)?;
+    let generations = match part {
+        Part::One => day11::generations(&start, Layout::adjacent_occupied_seats, 4),
+        Part::Two => day11::generations(&start, Layout::visible_occupied_seats, 5),
+    };
+    let mut recording = Recording::new();
+    for (round, layout) in generations.enumerate() {
+        let occupied = layout.count(Tile::OccupiedSeat);
+        recording.record(format!("Round {}: {} occupied", round, occupied), &layout);
+    }
+    Ok(recording)
+}
+
+fn conway_cubes(input: &str, part: Part) -> Result<Recording, ParseError> {
+    let start = day17::new_grid(input)?;
+    let mut recording = Recording::new();
+    let title = |cycle, active| format!("Cycle {}: {} active", cycle, active);
+    match part {
+        Part::One => {
+            let mut grid = start;
+            recording.record(title(0, grid.active()), &grid);
+            for cycle in 1..=day17::CYCLES {
+                grid = day17::iterate(&grid);
+                recording.record(title(cycle, grid.active()), &grid);
+            }
+        }
+        Part::Two => {
+            let mut grid = day17::Grid4::embed(&start);
+            recording.record(title(0, grid.active()), &grid);
+            for cycle in 1..=day17::CYCLES {
+                grid = day17::iterate4(&grid);
+                recording.record(title(cycle, grid.active()), &grid);
+            }
+        }
+    }
+    Ok(recording)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::examples;
+
+    fn example(day: usize) -> String {
+        examples::text(day, "example.txt")
+    }
+
+    #[test]
+    fn seating_rounds() {
+        let recorder = find(11).unwrap();
+        let recording = recorder.record(&example(11), Part::One).unwrap();
+        assert_eq!(recording.frames.len(), 6);
+        assert_eq!(recording.frames[0].picture, example(11).trim_end());
+        assert_eq!(recording.frames[5].title, "Round 5: 37 occupied");
+
+        let recording = recorder.record(&example(11), Part::Two).unwrap();
+        assert_eq!(
+            recording.frames.last().unwrap().title,
+            "Round 6: 26 occupied"
+        );
+    }
+
+    #[test]
+    fn conway_cycles() {
+        let recorder = find(17).unwrap();
+        let recording = recorder.record(&example(17), Part::One).unwrap();
+        assert_eq!(recording.frames.len(), 7);
+        assert_eq!(recording.frames[0].picture, "z=0\n.#.\n..#\n###");
+        assert_eq!(recording.frames[6].title, "Cycle 6: 112 active");
+
+        let recording = recorder.record(&example(17), Part::Two).unwrap();
+        assert!(recording.frames[1].picture.starts_with("z=-1, w=-1\n"));
+        assert_eq!(recording.frames[6].title, "Cycle 6: 848 active");
+    }
+
+    #[test]
+    fn export_and_play() {
+        let mut recording = Recording::new();
+        recording.record("First", &"#.\n.#\n");
+        recording.record("Second", &"..\n..");
+        assert_eq!(
+            recording.export(),
+            "First\n-----\n#.\n.#\n\nSecond\n------\n..\n..\n"
+        );
+
+        let mut out = Vec::new();
+        recording.play(&mut out, Duration::ZERO).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.starts_with("\x1b[?25l\x1b[2J\x1b[HFirst [1/2]\x1b[K\n#.\x1b[K\n"));
+        assert!(out.contains("\x1b[HSecond [2/2]\x1b[K\n..\x1b[K\n..\x1b[K\n\x1b[J"));
+        assert!(out.ends_with("\x1b[?25h"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(find(11).unwrap().record("L.x", Part::One).is_err());
+        assert!(find(12).is_none());
+    }
+}
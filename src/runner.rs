@@ -0,0 +1,184 @@
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::days::{Day, Part};
+use crate::{Answer, InputSource};
+
+/// How a part fared when run alongside the others.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Solved,
+    /// The input couldn't be read or parsed.
+    Failed(String),
+    Panicked(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct Outcome {
+    pub day: usize,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    /// Time spent solving the part; reading and parsing the input isn't counted.
+    pub elapsed: Duration,
+    pub status: Status,
+}
+
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    match payload.downcast::<String>() {
+        Ok(s) => *s,
+        Err(payload) => match payload.downcast::<&str>() {
+            Ok(s) => s.to_string(),
+            Err(_) => "(no message)".to_owned(),
+        },
+    }
+}
+
+fn run_day(day: &Day, parts: &[Part], source: InputSource) -> Vec<Outcome> {
+    let outcome = |part, answer, elapsed, status| Outcome {
+        day: day.number,
+        part,
+        answer,
+        elapsed,
+        status,
+    };
+    let parsed = panic::catch_unwind(|| day.parse(&source.try_content()?));
+    let parsed = match parsed {
+        Ok(Ok(parsed)) => parsed,
+        Ok(Err(e)) => {
+            let status = Status::Failed(e.to_string());
+            return parts
+                .iter()
+                .map(|&p| outcome(p, None, Duration::default(), status.clone()))
+                .collect();
+        }
+        Err(payload) => {
+            let status = Status::Panicked(panic_message(payload));
+            return parts
+                .iter()
+                .map(|&p| outcome(p, None, Duration::default(), status.clone()))
+                .collect();
+        }
+    };
+    // Each part gets its own chance: part 1 falling over says nothing about part 2.
+    parts
+        .iter()
+        .map(|&part| {
+            let start = Instant::now();
+            let answer = panic::catch_unwind(AssertUnwindSafe(|| day.solve(part, &parsed)));
+            let elapsed = start.elapsed();
+            match answer {
+                Ok(answer) => outcome(part, Some(answer), elapsed, Status::Solved),
+                Err(payload) => outcome(
+                    part,
+                    None,
+                    elapsed,
+                    Status::Panicked(panic_message(payload)),
+                ),
+            }
+        })
+        .collect()
+}
+
+/// Run the given days on `jobs` threads, catching any panics. The outcomes come back in the same
+/// order as `days` and `parts`, however the work was scheduled.
+pub fn run_days(
+    days: &[&Day],
+    parts: &[Part],
+    jobs: usize,
+    input: impl Fn(usize) -> InputSource + Sync,
+) -> Vec<Outcome> {
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(Vec::new());
+    thread::scope(|scope| {
+        for _ in 0..jobs.clamp(1, days.len().max(1)) {
+            scope.spawn(|| loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let day = match days.get(index) {
+                    Some(day) => day,
+                    None => break,
+                };
+                let outcomes = run_day(day, parts, input(day.number));
+                results.lock().unwrap().push((index, outcomes));
+            });
+        }
+    });
+    let mut results = results.into_inner().unwrap();
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().flat_map(|(_, o)| o).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::{self, PARTS};
+    use crate::{ParseError, Solution};
+
+    /// Answers part 1 with its input, and always panics on part 2.
+    struct Fragile;
+
+    impl Solution for Fragile {
+        type Parsed = String;
+
+        fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+            Ok(input.trim().to_owned())
+        }
+
+        fn part1(input: &Self::Parsed) -> Answer {
+            Answer::Text(input.clone())
+        }
+
+        fn part2(_: &Self::Parsed) -> Answer {
+            panic!("part 2 is broken")
+        }
+    }
+
+    static FRAGILE: Day = Day::new::<Fragile>(99);
+
+    #[test]
+    fn failures_are_contained() {
+        let to_run = [&FRAGILE, days::find(1).unwrap(), days::find(12).unwrap()];
+        let outcomes = run_days(&to_run, &PARTS, 2, |day| match day {
+            1 => InputSource::from("1721\n979\n366\n299\n675\n1456\n"),
+            99 => InputSource::from("fine\n"),
+            _ => InputSource::from("Q7\n"),
+        });
+        let summary: Vec<_> = outcomes
+            .iter()
+            .map(|o| (o.day, o.part, o.answer.clone(), o.status.clone()))
+            .collect();
+
+        assert_eq!(summary.len(), 6);
+        assert_eq!(
+            summary[0],
+            (
+                99,
+                Part::One,
+                Some(Answer::Text("fine".into())),
+                Status::Solved
+            )
+        );
+        assert_eq!(
+            summary[1],
+            (
+                99,
+                Part::Two,
+                None,
+                Status::Panicked("part 2 is broken".into())
+            )
+        );
+        assert_eq!(
+            summary[2],
+            (1, Part::One, Some(Answer::Number(514579)), Status::Solved)
+        );
+        assert_eq!(summary[3].2, Some(Answer::Number(241861950)));
+        for (day, _, answer, status) in &summary[4..] {
+            assert_eq!(*day, 12);
+            assert_eq!(*answer, None);
+            assert!(matches!(status, Status::Failed(_)));
+        }
+    }
+}
@@ -0,0 +1,183 @@
+//! A cursor for picking apart a line of input, which knows where it is so that it can say exactly
+//! where things went wrong.
+
+use std::str::FromStr;
+
+use crate::error::ParseError;
+
+pub type Result<T> = std::result::Result<T, ParseError>;
+
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    input: &'a str,
+    rest: &'a str,
+}
+
+impl<'a> Scanner<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Self { input, rest: input }
+    }
+
+    /// Everything not yet consumed.
+    pub fn rest(&self) -> &'a str {
+        self.rest
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rest.is_empty()
+    }
+
+    pub fn peek(&self) -> Option<char> {
+        self.rest.chars().next()
+    }
+
+    /// An error at the current position, blaming the word (or failing that, the character) found
+    /// there.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let word = self
+            .rest
+            .find(char::is_whitespace)
+            .map_or(self.rest, |end| &self.rest[..end]);
+        let found = match (word, self.rest.chars().next()) {
+            ("", Some(c)) => &self.rest[..c.len_utf8()],
+            _ => word,
+        };
+        ParseError::at(self.input, found, expected)
+    }
+
+    /// Consume `literal`, which must come next.
+    pub fn literal(&mut self, literal: &str) -> Result<()> {
+        match self.rest.strip_prefix(literal) {
+            Some(rest) => {
+                self.rest = rest;
+                Ok(())
+            }
+            None => Err(self.error(format!("{:?}", literal))),
+        }
+    }
+
+    /// Consume characters for as long as `f` likes them; possibly none at all.
+    pub fn take_while(&mut self, f: impl Fn(char) -> bool) -> &'a str {
+        let end = self.rest.find(|c| !f(c)).unwrap_or(self.rest.len());
+        let (taken, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        taken
+    }
+
+    /// Consume characters up to `delimiter`, which is left in place. It's an error if the
+    /// delimiter never turns up.
+    pub fn until(&mut self, delimiter: &str) -> Result<&'a str> {
+        match self.rest.find(delimiter) {
+            Some(end) => {
+                let (taken, rest) = self.rest.split_at(end);
+                self.rest = rest;
+                Ok(taken)
+            }
+            None => {
+                let end = Self {
+                    input: self.input,
+                    rest: &self.rest[self.rest.len()..],
+                };
+                Err(end.error(format!("{:?}", delimiter)))
+            }
+        }
+    }
+
+    /// Consume a single character.
+    pub fn char(&mut self) -> Result<char> {
+        let c = self.peek().ok_or_else(|| self.error("a character"))?;
+        self.rest = &self.rest[c.len_utf8()..];
+        Ok(c)
+    }
+
+    /// Consume a decimal number, with an optional sign.
+    pub fn number<T: FromStr>(&mut self) -> Result<T> {
+        let start = self.clone();
+        let sign = self.rest.starts_with(['+', '-']) as usize;
+        let digits = self.rest[sign..]
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(self.rest.len() - sign);
+        let (number, rest) = self.rest.split_at(sign + digits);
+        match number.parse() {
+            Ok(n) if digits > 0 => {
+                self.rest = rest;
+                Ok(n)
+            }
+            _ => Err(start.error("a number")),
+        }
+    }
+
+    /// Consume one or more things separated by `separator`.
+    pub fn separated<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Self) -> Result<T>,
+    ) -> Result<Vec<T>> {
+        let mut items = vec![item(self)?];
+        while self.literal(separator).is_ok() {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Check that everything has been consumed.
+    pub fn end(&self) -> Result<()> {
+        if self.rest.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("the end of the line"))
+        }
+    }
+}
+
+/// A line holding a number and nothing else.
+pub fn number_line<T: FromStr>(line: &str) -> Result<T> {
+    let mut s = Scanner::new(line);
+    let n = s.number()?;
+    s.end()?;
+    Ok(n)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scanning() {
+        let mut s = Scanner::new("1-3 a: abcde");
+        assert_eq!(s.number::<usize>(), Ok(1));
+        s.literal("-").unwrap();
+        assert_eq!(s.number::<i32>(), Ok(3));
+        s.literal(" ").unwrap();
+        assert_eq!(s.char(), Ok('a'));
+        assert_eq!(s.until(" "), Ok(":"));
+        s.literal(" ").unwrap();
+        assert_eq!(s.take_while(char::is_alphabetic), "abcde");
+        assert!(s.end().is_ok());
+        assert_eq!(Scanner::new("-12x").number::<i8>(), Ok(-12));
+        let mut s = Scanner::new("1,2,3;");
+        assert_eq!(s.separated(",", Scanner::number), Ok(vec![1, 2, 3]));
+        assert_eq!(s.rest(), ";");
+    }
+
+    #[test]
+    fn errors() {
+        let mut s = Scanner::new("mem[8] = x1");
+        s.literal("mem[").unwrap();
+        let e = s.literal("]").unwrap_err();
+        assert_eq!(
+            (e.column, e.expected.as_str(), e.found.as_str()),
+            (5, "\"]\"", "8]")
+        );
+        s.until(" = ").unwrap();
+        s.literal(" = ").unwrap();
+        let e = s.number::<u64>().unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (10, "x1"));
+        let e = s.until(";").unwrap_err();
+        assert_eq!((e.column, e.found.as_str()), (12, ""));
+        assert_eq!(Scanner::new("a b").end().unwrap_err().found, "a");
+        assert_eq!(Scanner::new(" b").end().unwrap_err().found, " ");
+        assert!(Scanner::new("-").number::<i32>().is_err());
+        assert!(Scanner::new("300").number::<u8>().is_err());
+    }
+}
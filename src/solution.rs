@@ -0,0 +1,96 @@
+use std::convert::TryFrom;
+use std::fmt;
+
+use serde::{Serialize, Serializer};
+
+use crate::ParseError;
+
+/// A puzzle answer, as it would be typed into the website.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => f.write_str(s),
+            Answer::Unsolved => f.write_str("(unsolved)"),
+        }
+    }
+}
+
+/// Numbers stay numbers and text stays text, so JSON consumers needn't guess; an unsolved part is
+/// `null`.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        match self {
+            Answer::Number(n) => serializer.serialize_i128(*n),
+            Answer::Text(s) => serializer.serialize_str(s),
+            Answer::Unsolved => serializer.serialize_none(),
+        }
+    }
+}
+
+macro_rules! answer_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    Answer::Number(n as i128)
+                }
+            }
+        )*
+    };
+}
+
+answer_from_integer!(u16, u32, u64, usize, i32, i64, isize);
+
+/// Too big for a number, the answer is still right as text.
+impl From<u128> for Answer {
+    fn from(n: u128) -> Self {
+        i128::try_from(n).map_or_else(|_| Answer::Text(n.to_string()), Answer::Number)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// One day's puzzle. The input is parsed once and then shared between both parts.
+pub trait Solution {
+    type Parsed: 'static;
+
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError>;
+    fn part1(parsed: &Self::Parsed) -> Answer;
+    fn part2(parsed: &Self::Parsed) -> Answer;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn json() {
+        let json = |a: Answer| serde_json::to_string(&a).unwrap();
+        assert_eq!(json(Answer::Number(230903629977901)), "230903629977901");
+        assert_eq!(json(Answer::Text("abc".to_owned())), "\"abc\"");
+        assert_eq!(json(Answer::Unsolved), "null");
+    }
+
+    #[test]
+    fn wide_numbers() {
+        assert_eq!(Answer::from(u128::MAX >> 1), Answer::Number(i128::MAX));
+        let too_big = Answer::from(u128::MAX);
+        assert_eq!(too_big, Answer::Text(u128::MAX.to_string()));
+        assert_eq!(
+            too_big.to_string(),
+            "340282366920938463463374607431768211455"
+        );
+    }
+}
@@ -0,0 +1,273 @@
+//! Making sense of what the puzzle server says about a submitted answer, and remembering it, so
+//! that an answer it has already turned down is never sent again.
+
+use std::env;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::days::Part;
+use crate::error::{Error, Result};
+
+/// The server's verdict on an answer.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "verdict")]
+pub enum Response {
+    Right,
+    /// Wrong, with no hint as to which way.
+    Wrong,
+    TooHigh,
+    TooLow,
+    /// Too soon after the previous attempt; the answer wasn't considered at all.
+    RateLimited {
+        wait_secs: Option<u64>,
+    },
+    /// The part has been solved already, or the one before it hasn't been yet.
+    WrongLevel,
+}
+
+impl Response {
+    /// Read the verdict out of the page the server sends back.
+    pub fn parse(page: &str) -> Option<Self> {
+        if page.contains("That's the right answer") {
+            Some(Response::Right)
+        } else if page.contains("That's not the right answer") {
+            if page.contains("your answer is too high") {
+                Some(Response::TooHigh)
+            } else if page.contains("your answer is too low") {
+                Some(Response::TooLow)
+            } else {
+                Some(Response::Wrong)
+            }
+        } else if page.contains("You gave an answer too recently") {
+            Some(Response::RateLimited {
+                wait_secs: parse_wait(page),
+            })
+        } else if page.contains("You don't seem to be solving the right level") {
+            Some(Response::WrongLevel)
+        } else {
+            None
+        }
+    }
+}
+
+/// The wait in `You have 4m 32s left to wait`, in seconds.
+fn parse_wait(page: &str) -> Option<u64> {
+    let start = page.find("You have ")? + "You have ".len();
+    let end = start + page[start..].find(" left to wait")?;
+    page[start..end]
+        .split_whitespace()
+        .map(|amount| {
+            let (number, unit) = amount.split_at(amount.len().checked_sub(1)?);
+            let scale = match unit {
+                "h" => 3600,
+                "m" => 60,
+                "s" => 1,
+                _ => return None,
+            };
+            number.parse::<u64>().ok().map(|n| n * scale)
+        })
+        .sum()
+}
+
+impl fmt::Display for Response {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Response::Right => f.write_str("right"),
+            Response::Wrong => f.write_str("wrong"),
+            Response::TooHigh => f.write_str("too high"),
+            Response::TooLow => f.write_str("too low"),
+            Response::RateLimited {
+                wait_secs: Some(secs),
+            } => write!(
+                f,
+                "rate limited; try again in {}m {}s",
+                secs / 60,
+                secs % 60
+            ),
+            Response::RateLimited { wait_secs: None } => f.write_str("rate limited"),
+            Response::WrongLevel => {
+                f.write_str("not for the level being solved; is it solved already?")
+            }
+        }
+    }
+}
+
+/// One answer sent to the server, and what it made of it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Attempt {
+    pub day: usize,
+    pub part: Part,
+    pub answer: String,
+    pub response: Response,
+    /// When it was sent, in seconds since the Unix epoch.
+    pub sent_at: u64,
+}
+
+/// Every answer we've submitted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct History {
+    pub attempts: Vec<Attempt>,
+}
+
+impl History {
+    pub fn default_path() -> PathBuf {
+        env::current_dir()
+            .unwrap_or_default()
+            .join("submissions.json")
+    }
+
+    /// Load the history; a file which doesn't exist yet means nothing has been submitted.
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(source) => {
+                return Err(Error::Io {
+                    path: path.to_owned(),
+                    source,
+                })
+            }
+        };
+        serde_json::from_str(&text).map_err(|e| {
+            Error::Parse(format!(
+                "{} isn't a submission history: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self).expect("a history is always serialisable");
+        fs::write(path, json + "\n").map_err(|source| Error::Io {
+            path: path.to_owned(),
+            source,
+        })
+    }
+
+    pub fn record(&mut self, day: usize, part: Part, answer: &str, response: Response) {
+        let sent_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        self.attempts.push(Attempt {
+            day,
+            part,
+            answer: answer.to_owned(),
+            response,
+            sent_at,
+        });
+    }
+
+    /// What the server would say to `answer`, if earlier attempts already tell us. Besides
+    /// repeats, an answer is known to be wrong if a different one was right, or if it's beyond a
+    /// number that was too high or too low.
+    pub fn known(&self, day: usize, part: Part, answer: &str) -> Option<Response> {
+        let number = answer.parse::<i128>().ok();
+        let mut known = None;
+        for attempt in self
+            .attempts
+            .iter()
+            .filter(|a| a.day == day && a.part == part)
+        {
+            let beyond =
+                |accept: fn(i128, i128) -> bool| match (number, attempt.answer.parse::<i128>()) {
+                    (Some(n), Ok(bound)) => accept(n, bound),
+                    _ => false,
+                };
+            let verdict = match &attempt.response {
+                Response::RateLimited { .. } | Response::WrongLevel => None,
+                response if attempt.answer == answer => Some(response.clone()),
+                Response::Right => Some(Response::Wrong),
+                Response::TooHigh if beyond(|n, bound| n >= bound) => Some(Response::TooHigh),
+                Response::TooLow if beyond(|n, bound| n <= bound) => Some(Response::TooLow),
+                _ => None,
+            };
+            // A repeat is the most specific thing we can say; keep looking for one.
+            if verdict.is_some() {
+                known = verdict;
+                if attempt.answer == answer {
+                    break;
+                }
+            }
+        }
+        known
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn responses() {
+        let page = |text: &str| format!("<main>\n<article><p>{}</p></article>\n</main>", text);
+        let check = |text: &str| Response::parse(&page(text));
+        assert_eq!(
+            check(
+                "That's the right answer!  You are one gold star closer to saving your vacation."
+            ),
+            Some(Response::Right)
+        );
+        assert_eq!(
+            check("That's not the right answer; your answer is too high.  If you're stuck, ..."),
+            Some(Response::TooHigh)
+        );
+        assert_eq!(
+            check("That's not the right answer; your answer is too low.  Please wait one minute."),
+            Some(Response::TooLow)
+        );
+        assert_eq!(
+            check("That's not the right answer.  If you're stuck, make sure you're using the full input data"),
+            Some(Response::Wrong)
+        );
+        assert_eq!(
+            check("You gave an answer too recently; you have to wait after submitting an answer \
+                   before trying again.  You have 4m 32s left to wait. [<a href=\"/2020/day/1\">Return to Day 1</a>]"),
+            Some(Response::RateLimited { wait_secs: Some(272) })
+        );
+        assert_eq!(
+            check("You gave an answer too recently.  You have 9s left to wait."),
+            Some(Response::RateLimited { wait_secs: Some(9) })
+        );
+        assert_eq!(
+            check("You don't seem to be solving the right level.  Did you already complete it?"),
+            Some(Response::WrongLevel)
+        );
+        assert_eq!(check("Puzzle inputs differ by user."), None);
+    }
+
+    #[test]
+    fn what_history_knows() {
+        let mut history = History::default();
+        history.record(1, Part::One, "100", Response::TooHigh);
+        history.record(1, Part::One, "20", Response::TooLow);
+        history.record(1, Part::One, "abc", Response::Wrong);
+        history.record(
+            1,
+            Part::One,
+            "50",
+            Response::RateLimited { wait_secs: None },
+        );
+        history.record(1, Part::Two, "7", Response::Right);
+
+        let known = |part, answer| history.known(1, part, answer);
+        assert_eq!(known(Part::One, "100"), Some(Response::TooHigh));
+        assert_eq!(known(Part::One, "150"), Some(Response::TooHigh));
+        assert_eq!(known(Part::One, "-3"), Some(Response::TooLow));
+        assert_eq!(known(Part::One, "abc"), Some(Response::Wrong));
+        assert_eq!(known(Part::One, "50"), None);
+        assert_eq!(known(Part::One, "xyz"), None);
+        assert_eq!(known(Part::Two, "7"), Some(Response::Right));
+        assert_eq!(known(Part::Two, "8"), Some(Response::Wrong));
+        assert_eq!(history.known(2, Part::One, "100"), None);
+
+        let path = env::temp_dir().join("aoc-2020-submissions.json");
+        history.save(&path).unwrap();
+        assert_eq!(History::load(&path).unwrap(), history);
+        fs::remove_file(path).unwrap();
+    }
+}
@@ -0,0 +1,232 @@
+//! Watching a day's sources and input for changes, by polling, and re-running its tests and
+//! solver through cargo whenever they change. Only changes in the answers are worth reporting.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::SystemTime;
+
+use crate::days::Part;
+
+/// What we know about a file without reading it: enough to notice that it's been saved.
+type Stamp = Option<(SystemTime, u64)>;
+
+/// The state of every file a day depends on. Files which don't exist are recorded too, so that
+/// creating one counts as a change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Stamp>);
+
+impl Snapshot {
+    /// Look at the Rust sources under `root/src`, other days' modules aside, and the day's input.
+    pub fn take(root: &Path, day: usize, input: &Path) -> Self {
+        let mut files = BTreeMap::new();
+        let mut sources = Vec::new();
+        collect_sources(&root.join("src"), &mut sources);
+        let own = format!("day{}.rs", day);
+        for path in sources {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            if !is_day_module(&name) || name == own {
+                files.insert(path.clone(), stamp(&path));
+            }
+        }
+        files.insert(input.to_owned(), stamp(input));
+        Self(files)
+    }
+
+    pub fn paths(&self) -> impl Iterator<Item = &Path> {
+        self.0.keys().map(PathBuf::as_path)
+    }
+
+    /// The files which differ between the two snapshots.
+    pub fn changes<'a>(&'a self, later: &'a Snapshot) -> Vec<&'a Path> {
+        let mut changed: Vec<&Path> = later
+            .0
+            .iter()
+            .filter(|(path, stamp)| self.0.get(*path) != Some(stamp))
+            .map(|(path, _)| path.as_path())
+            .collect();
+        changed.extend(
+            self.0
+                .keys()
+                .filter(|path| !later.0.contains_key(*path))
+                .map(PathBuf::as_path),
+        );
+        changed
+    }
+}
+
+/// `day7.rs` is a day's module, whereas `lib20.rs` and `days.rs` are shared.
+fn is_day_module(name: &str) -> bool {
+    name.strip_prefix("day")
+        .and_then(|rest| rest.strip_suffix(".rs"))
+        .is_some_and(|n| !n.is_empty() && n.bytes().all(|b| b.is_ascii_digit()))
+}
+
+fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_sources(&path, sources);
+        } else if path.extension().is_some_and(|e| e == "rs") {
+            sources.push(path);
+        }
+    }
+}
+
+fn stamp(path: &Path) -> Stamp {
+    let metadata = fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
+
+/// Each part's answer, as printed.
+pub type Answers = BTreeMap<Part, String>;
+
+/// One line per part whose answer is new or has changed.
+pub fn answer_diff(before: &Answers, after: &Answers) -> Vec<String> {
+    after
+        .iter()
+        .filter_map(|(part, answer)| match before.get(part) {
+            Some(old) if old == answer => None,
+            Some(old) => Some(format!("part {}: {} -> {}", part, old, answer)),
+            None => Some(format!("part {}: {}", part, answer)),
+        })
+        .collect()
+}
+
+/// Read the answers out of `aoc run --format json`.
+pub fn parse_answers(json_lines: &str) -> Answers {
+    json_lines
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|record| {
+            let part = match record["part"].as_u64()? {
+                1 => Part::One,
+                2 => Part::Two,
+                _ => return None,
+            };
+            let answer = match &record["answer"] {
+                serde_json::Value::String(s) => s.clone(),
+                serde_json::Value::Null => "(unsolved)".to_owned(),
+                other => other.to_string(),
+            };
+            Some((part, answer))
+        })
+        .collect()
+}
+
+fn cargo(root: &Path) -> Command {
+    let mut command = Command::new(option_env!("CARGO").unwrap_or("cargo"));
+    command.current_dir(root);
+    command
+}
+
+/// The output of a command which failed, or why it couldn't be run at all.
+fn output_of(command: &mut Command) -> Result<String, String> {
+    let output = command
+        .output()
+        .map_err(|e: io::Error| format!("couldn't run cargo: {}", e))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    if output.status.success() {
+        Ok(stdout)
+    } else {
+        Err(format!(
+            "{}{}",
+            stdout,
+            String::from_utf8_lossy(&output.stderr)
+        ))
+    }
+}
+
+/// Run the day's unit tests; on failure, say what cargo said.
+pub fn run_tests(root: &Path, day: usize) -> Result<(), String> {
+    output_of(
+        cargo(root)
+            .args(["test", "--quiet", "--lib"])
+            .arg(format!("day{}::", day)),
+    )
+    .map(|_| ())
+}
+
+/// Rebuild if need be, then solve the day.
+pub fn run_solver(root: &Path, day: usize, input: &Path) -> Result<Answers, String> {
+    let output = output_of(
+        cargo(root)
+            .args(["run", "--quiet", "--release", "--bin", "aoc", "--", "run"])
+            .arg(day.to_string())
+            .arg("--input")
+            .arg(input)
+            .args(["--format", "json"]),
+    )?;
+    Ok(parse_answers(&output))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::env;
+
+    #[test]
+    fn snapshots() {
+        let root = env::temp_dir().join("aoc-2020-watch-test");
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("src/bin")).unwrap();
+        for file in &["src/lib.rs", "src/day3.rs", "src/day4.rs", "src/bin/aoc.rs"] {
+            fs::write(root.join(file), "// nothing yet\n").unwrap();
+        }
+        let input = root.join("input/day3.txt");
+
+        let before = Snapshot::take(&root, 3, &input);
+        let names: Vec<_> = before
+            .paths()
+            .map(|p| p.strip_prefix(&root).unwrap())
+            .collect();
+        let expected: Vec<&Path> = [
+            "input/day3.txt",
+            "src/bin/aoc.rs",
+            "src/day3.rs",
+            "src/lib.rs",
+        ]
+        .iter()
+        .map(Path::new)
+        .collect();
+        assert_eq!(names, expected);
+        assert!(before.changes(&Snapshot::take(&root, 3, &input)).is_empty());
+
+        fs::write(root.join("src/day4.rs"), "// someone else's problem\n").unwrap();
+        fs::write(root.join("src/day3.rs"), "// getting somewhere\n").unwrap();
+        fs::create_dir_all(root.join("input")).unwrap();
+        fs::write(&input, "..#\n").unwrap();
+        let after = Snapshot::take(&root, 3, &input);
+        assert_eq!(
+            before.changes(&after),
+            vec![input.as_path(), &root.join("src/day3.rs")]
+        );
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn answers() {
+        let json = "{\"day\":20,\"part\":1,\"answer\":123,\"elapsed_ms\":1.5}\n\
+                    {\"day\":20,\"part\":2,\"answer\":null,\"elapsed_ms\":0.0}\n";
+        let before = parse_answers(json);
+        assert_eq!(before[&Part::One], "123");
+        assert_eq!(before[&Part::Two], "(unsolved)");
+        assert_eq!(answer_diff(&Answers::new(), &before).len(), 2);
+
+        let after = parse_answers(
+            "{\"day\":20,\"part\":1,\"answer\":123,\"elapsed_ms\":1.4}\n\
+             {\"day\":20,\"part\":2,\"answer\":\"abc\",\"elapsed_ms\":0.1}\n",
+        );
+        assert_eq!(
+            answer_diff(&before, &after),
+            vec!["part 2: (unsolved) -> abc"]
+        );
+        assert!(answer_diff(&after, &after).is_empty());
+    }
+}